@@ -46,4 +46,6 @@ fn main() {
     }
 
     assert_eq!(*company.resource(&ceo_id).unwrap(), start_resource);
+    assert_eq!(company.total_supply(), start_resource);
+    company.check_supply().unwrap();
 }
@@ -0,0 +1,81 @@
+//! Applies arbitrary sequences of operations to a company and checks that resources are conserved
+//! and the hierarchy stays consistent.
+//!
+//! Run with `cargo fuzz run company_operations`.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use staff_lib::operation::Operation;
+use staff_lib::{Company, StaffEntity};
+
+/// Staff member with a small ID space, so operations often refer to existing members.
+#[derive(Debug, Clone, Arbitrary)]
+struct Manager {
+    id: u8,
+}
+
+impl StaffEntity for Manager {
+    type ID = u8;
+
+    fn get_id(&self) -> Self::ID {
+        self.id
+    }
+}
+
+fuzz_target!(|operations: Vec<Operation<Manager, u32>>| {
+    let mut company: Company<Manager, u64> = Company::new(Manager { id: 0 });
+
+    for operation in operations {
+        // amounts are widened so that minting can not overflow
+        let operation = match operation {
+            Operation::Hire {
+                staff,
+                supervisor_id,
+            } => Operation::Hire {
+                staff,
+                supervisor_id,
+            },
+            Operation::Fire { staff_id } => Operation::Fire { staff_id },
+            Operation::Transfer { from, to, amount } => Operation::Transfer {
+                from,
+                to,
+                amount: amount.into(),
+            },
+            Operation::Withdraw { staff_id, amount } => Operation::Withdraw {
+                staff_id,
+                amount: amount.into(),
+            },
+            Operation::Mint { amount } => Operation::Mint {
+                amount: amount.into(),
+            },
+            Operation::ChangeSupervisor {
+                staff_id,
+                supervisor_id,
+            } => Operation::ChangeSupervisor {
+                staff_id,
+                supervisor_id,
+            },
+        };
+        let _ = company.apply(operation);
+
+        company.check_supply().unwrap();
+        let staff = company.get_all_staff();
+        for id in staff.iter() {
+            for subordinate in company.subordinates(id).into_iter().flatten() {
+                assert_eq!(company.supervisor(subordinate), Some(id));
+            }
+
+            let mut current = id;
+            let mut steps = 0;
+            while let Some(supervisor) = company.supervisor(current) {
+                assert!(company.subordinates(supervisor).unwrap().contains(current));
+                current = supervisor;
+                steps += 1;
+                assert!(steps <= staff.len());
+            }
+            assert_eq!(*current, 0);
+        }
+    }
+});
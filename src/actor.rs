@@ -0,0 +1,502 @@
+//! Module defines an actor-style front end of a [`Company`]: the company is owned by a single
+//! task which executes [`Command`]s received over a channel and replies to every command
+//! separately.
+//!
+//! [`spawn`] runs the actor on a std thread and returns a blocking [`CompanyHandle`]. With the
+//! `tokio` feature `spawn_tokio` runs it on the blocking pool of tokio and returns an
+//! `AsyncCompanyHandle`. Other executors can drive a [`CompanyActor`] directly.
+//!
+//! The company is moved to the actor and back and never shared, so staff members, their IDs and
+//! resources only need to be `Send`.
+
+use crate::company::{Company, CompanyParts};
+use crate::errors::Error;
+use crate::resource::Resource;
+use crate::storage::{Backend, PersistentBackend};
+use crate::traits::StaffEntity;
+use std::marker::PhantomData;
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+#[cfg(feature = "tokio")]
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// One-shot reply channel of a [`Command`].
+pub struct Reply<T> {
+    send: Box<dyn FnOnce(T) + Send>,
+}
+
+impl<T: Send + 'static> Reply<T> {
+    /// Creates a reply sending the result with `send`.
+    pub fn new(send: impl FnOnce(T) + Send + 'static) -> Self {
+        Self {
+            send: Box::new(send),
+        }
+    }
+
+    /// Sends the result, it is dropped if the requester is gone.
+    pub fn send(self, value: T) {
+        (self.send)(value)
+    }
+}
+
+/// Reply to a [`Command`] which may fail.
+pub type ResultReply<T, V, R> = Reply<Result<T, Error<<V as StaffEntity>::ID, R>>>;
+
+/// Message executed by a [`CompanyActor`].
+///
+/// Every variant mirrors the [`Company`] method with the same name.
+pub enum Command<V: StaffEntity, R> {
+    Hire {
+        staff: V,
+        supervisor_id: V::ID,
+        reply: ResultReply<V::ID, V, R>,
+    },
+    Fire {
+        staff_id: V::ID,
+        reply: ResultReply<V, V, R>,
+    },
+    Transfer {
+        from: V::ID,
+        to: V::ID,
+        amount: R,
+        reply: ResultReply<(), V, R>,
+    },
+    Withdraw {
+        staff_id: V::ID,
+        amount: R,
+        reply: ResultReply<(), V, R>,
+    },
+    Mint {
+        amount: R,
+        reply: Reply<()>,
+    },
+    ChangeSupervisor {
+        staff_id: V::ID,
+        supervisor_id: V::ID,
+        reply: ResultReply<(), V, R>,
+    },
+    GetAllStaff {
+        reply: Reply<Vec<V::ID>>,
+    },
+    Resource {
+        staff_id: V::ID,
+        reply: Reply<Option<R>>,
+    },
+    Supervisor {
+        staff_id: V::ID,
+        reply: Reply<Option<V::ID>>,
+    },
+    Subordinates {
+        staff_id: V::ID,
+        reply: Reply<Option<Vec<V::ID>>>,
+    },
+    TotalSupply {
+        reply: Reply<R>,
+    },
+}
+
+/// Owner of a [`Company`] executing [`Command`]s one at a time.
+pub struct CompanyActor<V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    company: Company<V, R, B>,
+}
+
+impl<V, R, B> CompanyActor<V, R, B>
+where
+    V: StaffEntity + Send + 'static,
+    V::ID: Send + 'static,
+    R: Resource + Send + 'static,
+    B: Backend<V::ID>,
+{
+    /// Creates an actor owning `company`.
+    pub fn new(company: Company<V, R, B>) -> Self {
+        Self { company }
+    }
+
+    /// Executes a command and sends its reply.
+    pub fn handle(&mut self, command: Command<V, R>) {
+        let company = &mut self.company;
+        match command {
+            Command::Hire {
+                staff,
+                supervisor_id,
+                reply,
+            } => reply.send(company.hire(staff, &supervisor_id)),
+            Command::Fire { staff_id, reply } => reply.send(company.fire(&staff_id)),
+            Command::Transfer {
+                from,
+                to,
+                amount,
+                reply,
+            } => reply.send(company.transfer(&from, &to, amount)),
+            Command::Withdraw {
+                staff_id,
+                amount,
+                reply,
+            } => reply.send(company.withdraw(&staff_id, amount)),
+            Command::Mint { amount, reply } => {
+                company.mint(amount);
+                reply.send(())
+            }
+            Command::ChangeSupervisor {
+                staff_id,
+                supervisor_id,
+                reply,
+            } => reply.send(company.change_supervisor(&staff_id, &supervisor_id)),
+            Command::GetAllStaff { reply } => reply.send(company.get_all_staff()),
+            Command::Resource { staff_id, reply } => {
+                reply.send(company.resource(&staff_id).copied())
+            }
+            Command::Supervisor { staff_id, reply } => {
+                reply.send(company.supervisor(&staff_id).cloned())
+            }
+            Command::Subordinates { staff_id, reply } => {
+                reply.send(company.subordinates_in_order(&staff_id))
+            }
+            Command::TotalSupply { reply } => reply.send(company.total_supply()),
+        }
+    }
+
+    /// Consumes the actor returning the company.
+    pub fn into_company(self) -> Company<V, R, B> {
+        self.company
+    }
+}
+
+/// Runs an actor owning `company` on a new thread.
+///
+/// The thread stops once every clone of the returned handle is dropped.
+///
+/// # Returns
+/// - The handle sending commands to the actor.
+/// - The [`CompanyThread`] returning the company when joined.
+pub fn spawn<V, R, B>(company: Company<V, R, B>) -> (CompanyHandle<V, R>, CompanyThread<V, R, B>)
+where
+    V: StaffEntity + Send + 'static,
+    V::ID: Send + 'static,
+    R: Resource + Send + 'static,
+    B: Backend<V::ID> + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let parts = company.into_parts();
+    let thread = thread::spawn(move || {
+        let mut actor = CompanyActor::<V, R, B>::new(Company::from_parts(parts));
+        for command in receiver {
+            actor.handle(command);
+        }
+        actor.into_company().into_parts()
+    });
+
+    let backend = PhantomData;
+    (CompanyHandle { sender }, CompanyThread { thread, backend })
+}
+
+/// Thread of an actor started with [`spawn`].
+pub struct CompanyThread<V: StaffEntity, R, B = PersistentBackend> {
+    thread: JoinHandle<CompanyParts<V, R>>,
+    backend: PhantomData<fn() -> B>,
+}
+
+impl<V: StaffEntity, R: Resource, B: Backend<V::ID>> CompanyThread<V, R, B> {
+    /// Waits for the actor to stop, see [`JoinHandle::join`].
+    ///
+    /// # Returns
+    /// - The company owned by the actor.
+    ///
+    /// # Errors
+    /// - The panic payload if the actor thread panicked.
+    pub fn join(self) -> thread::Result<Company<V, R, B>> {
+        self.thread.join().map(Company::from_parts)
+    }
+}
+
+/// Blocking handle of an actor started with [`spawn`].
+///
+/// # Panics
+/// Every method panics if the actor thread has stopped, which only happens if it panicked.
+pub struct CompanyHandle<V: StaffEntity, R> {
+    sender: mpsc::Sender<Command<V, R>>,
+}
+
+impl<V: StaffEntity, R> Clone for CompanyHandle<V, R> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<V, R> CompanyHandle<V, R>
+where
+    V: StaffEntity + Send + 'static,
+    V::ID: Send + 'static,
+    R: Resource + Send + 'static,
+{
+    fn request<T: Send + 'static>(&self, command: impl FnOnce(Reply<T>) -> Command<V, R>) -> T {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let reply = Reply::new(move |value| {
+            let _ = sender.send(value);
+        });
+        self.sender
+            .send(command(reply))
+            .expect("company actor has stopped");
+        receiver.recv().expect("company actor has stopped")
+    }
+
+    /// See [`Company::hire`].
+    pub fn hire(&self, staff: V, supervisor_id: V::ID) -> Result<V::ID, Error<V::ID, R>> {
+        self.request(|reply| Command::Hire {
+            staff,
+            supervisor_id,
+            reply,
+        })
+    }
+
+    /// See [`Company::fire`].
+    pub fn fire(&self, staff_id: V::ID) -> Result<V, Error<V::ID, R>> {
+        self.request(|reply| Command::Fire { staff_id, reply })
+    }
+
+    /// See [`Company::transfer`].
+    pub fn transfer(&self, from: V::ID, to: V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        self.request(|reply| Command::Transfer {
+            from,
+            to,
+            amount,
+            reply,
+        })
+    }
+
+    /// See [`Company::withdraw`].
+    pub fn withdraw(&self, staff_id: V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        self.request(|reply| Command::Withdraw {
+            staff_id,
+            amount,
+            reply,
+        })
+    }
+
+    /// See [`Company::mint`].
+    pub fn mint(&self, amount: R) {
+        self.request(|reply| Command::Mint { amount, reply })
+    }
+
+    /// See [`Company::change_supervisor`].
+    pub fn change_supervisor(
+        &self,
+        staff_id: V::ID,
+        supervisor_id: V::ID,
+    ) -> Result<(), Error<V::ID, R>> {
+        self.request(|reply| Command::ChangeSupervisor {
+            staff_id,
+            supervisor_id,
+            reply,
+        })
+    }
+
+    /// See [`Company::get_all_staff`].
+    pub fn get_all_staff(&self) -> Vec<V::ID> {
+        self.request(|reply| Command::GetAllStaff { reply })
+    }
+
+    /// See [`Company::resource`].
+    pub fn resource(&self, staff_id: V::ID) -> Option<R> {
+        self.request(|reply| Command::Resource { staff_id, reply })
+    }
+
+    /// See [`Company::supervisor`].
+    pub fn supervisor(&self, staff_id: V::ID) -> Option<V::ID> {
+        self.request(|reply| Command::Supervisor { staff_id, reply })
+    }
+
+    /// See [`Company::subordinates_in_order`].
+    pub fn subordinates(&self, staff_id: V::ID) -> Option<Vec<V::ID>> {
+        self.request(|reply| Command::Subordinates { staff_id, reply })
+    }
+
+    /// See [`Company::total_supply`].
+    pub fn total_supply(&self) -> R {
+        self.request(|reply| Command::TotalSupply { reply })
+    }
+}
+
+/// Runs an actor owning `company` on the blocking pool of the current tokio runtime.
+///
+/// The actor stops once every clone of the returned handle is dropped.
+///
+/// # Returns
+/// - The handle sending commands to the actor.
+/// - The [`CompanyTask`] returning the company when awaited.
+///
+/// # Panics
+/// If called outside of a tokio runtime.
+#[cfg(feature = "tokio")]
+pub fn spawn_tokio<V, R, B>(
+    company: Company<V, R, B>,
+) -> (AsyncCompanyHandle<V, R>, CompanyTask<V, R, B>)
+where
+    V: StaffEntity + Send + 'static,
+    V::ID: Send + 'static,
+    R: Resource + Send + 'static,
+    B: Backend<V::ID> + 'static,
+{
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let parts = company.into_parts();
+    let task = tokio::task::spawn_blocking(move || {
+        let mut actor = CompanyActor::<V, R, B>::new(Company::from_parts(parts));
+        while let Some(command) = receiver.blocking_recv() {
+            actor.handle(command);
+        }
+        actor.into_company().into_parts()
+    });
+
+    let backend = PhantomData;
+    (AsyncCompanyHandle { sender }, CompanyTask { task, backend })
+}
+
+/// Task of an actor started with [`spawn_tokio`], resolving to the company owned by the actor
+/// or the [`tokio::task::JoinError`] if it panicked.
+#[cfg(feature = "tokio")]
+pub struct CompanyTask<V: StaffEntity, R, B = PersistentBackend> {
+    task: tokio::task::JoinHandle<CompanyParts<V, R>>,
+    backend: PhantomData<fn() -> B>,
+}
+
+#[cfg(feature = "tokio")]
+impl<V: StaffEntity, R: Resource, B: Backend<V::ID>> Future for CompanyTask<V, R, B> {
+    type Output = Result<Company<V, R, B>, tokio::task::JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.task)
+            .poll(cx)
+            .map(|result| result.map(Company::from_parts))
+    }
+}
+
+/// Asynchronous handle of an actor started with [`spawn_tokio`].
+///
+/// # Panics
+/// Every method panics if the actor task has stopped, which only happens if it panicked or the
+/// runtime shut down.
+#[cfg(feature = "tokio")]
+pub struct AsyncCompanyHandle<V: StaffEntity, R> {
+    sender: tokio::sync::mpsc::UnboundedSender<Command<V, R>>,
+}
+
+#[cfg(feature = "tokio")]
+impl<V: StaffEntity, R> Clone for AsyncCompanyHandle<V, R> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<V, R> AsyncCompanyHandle<V, R>
+where
+    V: StaffEntity + Send + 'static,
+    V::ID: Send + 'static,
+    R: Resource + Send + 'static,
+{
+    async fn request<T: Send + 'static>(
+        &self,
+        command: impl FnOnce(Reply<T>) -> Command<V, R>,
+    ) -> T {
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        let reply = Reply::new(move |value| {
+            let _ = sender.send(value);
+        });
+        self.sender
+            .send(command(reply))
+            .unwrap_or_else(|_| panic!("company actor has stopped"));
+        receiver.await.expect("company actor has stopped")
+    }
+
+    /// See [`Company::hire`].
+    pub async fn hire(&self, staff: V, supervisor_id: V::ID) -> Result<V::ID, Error<V::ID, R>> {
+        self.request(|reply| Command::Hire {
+            staff,
+            supervisor_id,
+            reply,
+        })
+        .await
+    }
+
+    /// See [`Company::fire`].
+    pub async fn fire(&self, staff_id: V::ID) -> Result<V, Error<V::ID, R>> {
+        self.request(|reply| Command::Fire { staff_id, reply })
+            .await
+    }
+
+    /// See [`Company::transfer`].
+    pub async fn transfer(&self, from: V::ID, to: V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        self.request(|reply| Command::Transfer {
+            from,
+            to,
+            amount,
+            reply,
+        })
+        .await
+    }
+
+    /// See [`Company::withdraw`].
+    pub async fn withdraw(&self, staff_id: V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        self.request(|reply| Command::Withdraw {
+            staff_id,
+            amount,
+            reply,
+        })
+        .await
+    }
+
+    /// See [`Company::mint`].
+    pub async fn mint(&self, amount: R) {
+        self.request(|reply| Command::Mint { amount, reply }).await
+    }
+
+    /// See [`Company::change_supervisor`].
+    pub async fn change_supervisor(
+        &self,
+        staff_id: V::ID,
+        supervisor_id: V::ID,
+    ) -> Result<(), Error<V::ID, R>> {
+        self.request(|reply| Command::ChangeSupervisor {
+            staff_id,
+            supervisor_id,
+            reply,
+        })
+        .await
+    }
+
+    /// See [`Company::get_all_staff`].
+    pub async fn get_all_staff(&self) -> Vec<V::ID> {
+        self.request(|reply| Command::GetAllStaff { reply }).await
+    }
+
+    /// See [`Company::resource`].
+    pub async fn resource(&self, staff_id: V::ID) -> Option<R> {
+        self.request(|reply| Command::Resource { staff_id, reply })
+            .await
+    }
+
+    /// See [`Company::supervisor`].
+    pub async fn supervisor(&self, staff_id: V::ID) -> Option<V::ID> {
+        self.request(|reply| Command::Supervisor { staff_id, reply })
+            .await
+    }
+
+    /// See [`Company::subordinates_in_order`].
+    pub async fn subordinates(&self, staff_id: V::ID) -> Option<Vec<V::ID>> {
+        self.request(|reply| Command::Subordinates { staff_id, reply })
+            .await
+    }
+
+    /// See [`Company::total_supply`].
+    pub async fn total_supply(&self) -> R {
+        self.request(|reply| Command::TotalSupply { reply }).await
+    }
+}
@@ -0,0 +1,67 @@
+//! Module defines the [`ApprovalPolicy`] for large transfers and pending [`TransferRequest`]s
+//! waiting for approval.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::reservation::ReservationId;
+
+/// Unique identifier of a [`TransferRequest`] inside a company.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestId(pub(crate) u64);
+
+/// Policy describing which transfers must be approved by supervisors of the sender.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ApprovalPolicy<R> {
+    /// Transfers of amounts above the threshold require approval.
+    pub threshold: R,
+    /// Number of distinct supervisors up the chain of command which must approve a transfer.
+    ///
+    /// It is capped at the number of supervisors the sender has, so the CEO's transfers never
+    /// require approval.
+    pub required_approvals: usize,
+}
+
+/// Transfer waiting for approval of the sender's supervisors.
+///
+/// The requested amount is reserved on the sender's balance until the request is executed,
+/// rejected or cancelled by the sender.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferRequest<ID: Eq + Hash, R> {
+    pub(crate) from: ID,
+    pub(crate) to: ID,
+    pub(crate) amount: R,
+    pub(crate) reservation_id: ReservationId,
+    pub(crate) approvals: HashSet<ID>,
+}
+
+impl<ID: Eq + Hash, R: Copy> TransferRequest<ID, R> {
+    /// Returns the ID of the sender.
+    pub fn from(&self) -> &ID {
+        &self.from
+    }
+
+    /// Returns the ID of the receiver.
+    pub fn to(&self) -> &ID {
+        &self.to
+    }
+
+    /// Returns the requested amount.
+    pub fn amount(&self) -> R {
+        self.amount
+    }
+
+    /// Returns the IDs of supervisors who approved the request.
+    pub fn approvals(&self) -> &HashSet<ID> {
+        &self.approvals
+    }
+}
+
+/// State of a [`TransferRequest`] after an approval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalStatus {
+    /// The request still waits for more approvals.
+    Pending { approvals: usize, required: usize },
+    /// The request collected enough approvals and the transfer was executed.
+    Executed,
+}
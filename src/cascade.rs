@@ -0,0 +1,32 @@
+//! Module defines the [`CascadeRule`] used by [`crate::Company::mint_and_cascade`] and the
+//! [`CascadeReport`] describing where the minted resources ended up.
+
+/// How resources passed down by a supervisor are split between its direct subordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CascadeWeights {
+    /// Every subordinate receives an equal part.
+    #[default]
+    Equal,
+    /// Subordinates receive parts proportional to the size of their subtrees (themselves
+    /// together with all their direct and indirect subordinates).
+    SubtreeSize,
+}
+
+/// Rule of splitting minted resources through the hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CascadeRule<R> {
+    /// Percentage of the received resources every supervisor keeps. Staff members without
+    /// subordinates keep everything they receive.
+    pub keep_percentage: R,
+    /// How the rest is split between direct subordinates.
+    pub weights: CascadeWeights,
+}
+
+/// Summary of a cascade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CascadeReport<ID, R> {
+    /// Amounts credited to staff members, supervisors go before their subordinates.
+    pub credited: Vec<(ID, R)>,
+    /// Resources added to the company's dust account by rounding.
+    pub dust: R,
+}
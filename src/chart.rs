@@ -0,0 +1,171 @@
+//! Module defines [`Chart`], an org chart of a company returned by [`Company::chart`] that
+//! renders to Graphviz DOT or Mermaid text.
+
+use crate::company::Company;
+use crate::errors::Error;
+use crate::resource::Resource;
+use crate::storage::{Backend, PersistentBackend, StaffSet};
+use crate::traits::StaffEntity;
+use core::fmt::Display;
+use core::fmt::Write;
+
+/// Listed staff member.
+struct Node<ID> {
+    staff_id: ID,
+    /// Lines of the label.
+    lines: Vec<String>,
+    /// Index of the supervisor node, `None` for the root.
+    parent: Option<usize>,
+}
+
+/// Org chart of a company or a subtree of it.
+///
+/// Staff members are listed from the root depth-first, subordinates ordered by ID, so the output
+/// is the same for the same company.
+pub struct Chart<'a, V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    company: &'a Company<V, R, B>,
+    root_id: Option<V::ID>,
+    label: Box<dyn Fn(&V) -> String + 'a>,
+    balances: bool,
+}
+
+impl<V, R, B> Company<V, R, B>
+where
+    V: StaffEntity,
+    V::ID: Ord + Display,
+    R: Resource + Display,
+    B: Backend<V::ID>,
+{
+    /// Returns the org chart of the whole company.
+    ///
+    /// # Arguments
+    /// - `label`: Formats the label of a staff member node.
+    pub fn chart<'a>(&'a self, label: impl Fn(&V) -> String + 'a) -> Chart<'a, V, R, B> {
+        Chart {
+            company: self,
+            root_id: self.ceo().map(|ceo| ceo.get_id()),
+            label: Box::new(label),
+            balances: false,
+        }
+    }
+}
+
+impl<'a, V, R, B> Chart<'a, V, R, B>
+where
+    V: StaffEntity,
+    V::ID: Ord + Display,
+    R: Resource + Display,
+    B: Backend<V::ID>,
+{
+    /// Limits the chart to the staff member and its direct and indirect subordinates.
+    ///
+    /// # Errors
+    /// - [`Error::StaffNotFound`] if the staff member does not exist.
+    pub fn subtree(mut self, root_id: &V::ID) -> Result<Self, Error<V::ID, R>> {
+        if self.company.get(root_id).is_none() {
+            return Err(Error::StaffNotFound {
+                staff_id: root_id.clone(),
+            });
+        }
+        self.root_id = Some(root_id.clone());
+        Ok(self)
+    }
+
+    /// Adds the balance of every staff member to its label.
+    pub fn with_balances(mut self) -> Self {
+        self.balances = true;
+        self
+    }
+
+    /// Renders the chart as a Graphviz DOT digraph, nodes are named by staff IDs.
+    pub fn to_dot(&self) -> String {
+        let nodes = self.walk();
+        let name = |node: &Node<V::ID>| escape_dot(&node.staff_id.to_string());
+
+        let mut out = String::from("digraph company {\n");
+        for node in nodes.iter() {
+            let label: Vec<String> = node.lines.iter().map(|line| escape_dot(line)).collect();
+            writeln!(
+                out,
+                "    \"{}\" [label=\"{}\"];",
+                name(node),
+                label.join("\\n")
+            )
+            .unwrap();
+        }
+        for node in nodes.iter() {
+            if let Some(parent) = node.parent {
+                writeln!(
+                    out,
+                    "    \"{}\" -> \"{}\";",
+                    name(&nodes[parent]),
+                    name(node)
+                )
+                .unwrap();
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the chart as a Mermaid top-down flowchart.
+    ///
+    /// Mermaid node names are restricted, so nodes are named `n0`, `n1`, ... in listing order.
+    pub fn to_mermaid(&self) -> String {
+        let nodes = self.walk();
+
+        let mut out = String::from("graph TD\n");
+        for (index, node) in nodes.iter().enumerate() {
+            let label: Vec<String> = node.lines.iter().map(|line| escape_mermaid(line)).collect();
+            writeln!(out, "    n{index}[\"{}\"]", label.join("<br/>")).unwrap();
+        }
+        for (index, node) in nodes.iter().enumerate() {
+            if let Some(parent) = node.parent {
+                writeln!(out, "    n{parent} --> n{index}").unwrap();
+            }
+        }
+        out
+    }
+
+    /// Returns the nodes in listing order.
+    fn walk(&self) -> Vec<Node<V::ID>> {
+        let mut nodes = Vec::new();
+        let Some(root_id) = &self.root_id else {
+            return nodes;
+        };
+
+        let mut stack = vec![(root_id.clone(), None)];
+        while let Some((staff_id, parent)) = stack.pop() {
+            let index = nodes.len();
+            let mut lines = vec![(self.label)(self.company.get(&staff_id).unwrap())];
+            if self.balances {
+                lines.push(self.company.resource(&staff_id).unwrap().to_string());
+            }
+
+            let mut subordinates: Vec<V::ID> = self
+                .company
+                .subordinate_set(&staff_id)
+                .into_iter()
+                .flat_map(StaffSet::iter)
+                .cloned()
+                .collect();
+            subordinates.sort_by(|a, b| b.cmp(a));
+            stack.extend(subordinates.into_iter().map(|id| (id, Some(index))));
+
+            nodes.push(Node {
+                staff_id,
+                lines,
+                parent,
+            });
+        }
+        nodes
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
@@ -1,16 +1,27 @@
+use crate::approval::{ApprovalPolicy, ApprovalStatus, RequestId, TransferRequest};
+use crate::cascade::{CascadeReport, CascadeRule, CascadeWeights};
 use crate::errors::Error;
+use crate::limits::{LimitKind, Limits};
+use crate::order::{CompareIds, StaffOrder};
+use crate::payroll::{Payout, PayrollReport, PayrollRule, Shortfall};
+use crate::reservation::{Reservation, ReservationId};
+use crate::resource::{allocate, Resource, Rounding};
+use crate::stats::{BalanceStats, CompanyStats};
+use crate::storage::{Backend, PersistentBackend, StaffMap, StaffSet};
+use crate::structure::{Move, StructureRule, StructureRules, Tree, Violation};
+use crate::sweep::{Sweep, SweepAmount, SweepReport, SweepRule, SweepTarget};
 use crate::traits::StaffEntity;
-use core::fmt::Debug;
-use num_traits::{FromPrimitive, Num, Zero};
-use std::collections::{HashMap, HashSet};
-use std::ops::Div;
+use num_traits::ToPrimitive;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, OnceLock};
 
 /// The `Company` struct organizes relations between company members and manages resources.
 ///
 /// # Generics
 /// - `V`: A type that implements the [`StaffEntity`] trait, representing a company staff member.
-/// - `R`: A numeric type that implements [`Num`], [`Copy`], [`Zero`], [`PartialOrd`], [`Div`], [`FromPrimitive`],
-///        and [`Debug`]. It is used to represent company funds.
+/// - `R`: A numeric type that implements [`Resource`]. It is used to represent company funds.
+/// - `B`: The [`Backend`] storing staff, balances and relations, [`PersistentBackend`] by
+///   default.
 ///
 /// # Fields
 /// - `ceo_id`: ID of the company's CEO.
@@ -18,38 +29,107 @@ use std::ops::Div;
 /// - `resources`: A mapping of staff IDs to their resources.
 /// - `supervisors`: A mapping from subordinate IDs to their supervisor IDs.
 /// - `subordinates`: A mapping from supervisor IDs to sets of their subordinates' IDs.
+/// - `rounding`: The [`Rounding`] mode applied when resources are split.
+/// - `dust`: Resources left over by rounding which do not belong to any staff member.
+/// - `minted`: Total amount of resources ever minted.
+/// - `burned`: Total amount of resources ever withdrawn from the company.
+/// - `reservations`: A mapping from reservation IDs to active reservations.
+/// - `reserved`: A mapping of staff IDs to the total amount reserved on their balance.
+/// - `next_reservation_id`: Identifier given to the next reservation.
+/// - `limits`: A mapping of staff IDs to their spending limits.
+/// - `spent`: A mapping of staff IDs to the amount spent during the current period.
+/// - `period`: Number of the current spending period.
+/// - `approval_policy`: The [`ApprovalPolicy`] for large transfers, if any.
+/// - `requests`: A mapping from request IDs to transfers waiting for approval.
+/// - `next_request_id`: Identifier given to the next transfer request.
+/// - `structure`: The [`StructureRules`] restricting the shape of the hierarchy.
+/// - `order`: The [`StaffOrder`] of listed staff members.
+/// - `compare_ids`: Function comparing staff IDs, set for [`StaffOrder::Sorted`].
+/// - `joined`: A mapping of staff IDs to the sequence number of their joining.
+/// - `next_joined`: Sequence number given to the next staff member.
+///
+/// `staff`, `resources`, `supervisors`, `subordinates` and `joined` are maps of the backend `B`,
+/// the sets of subordinates are sets of the backend. They are shared between clones of a company
+/// and copied on the first change. With the default [`PersistentBackend`] a change copies only
+/// the changed part of a map, so cloning and taking snapshots stays cheap however often the
+/// copies change. The other maps are persistent [`im::HashMap`]s for any backend. Staff members
+/// and sets of subordinates are shared one by one and copied when they are changed.
 ///
 /// # Usage
 /// The `Company` struct provides methods to hire and fire employees, manage their resources,
 /// and handle hierarchical relationships (supervisors and subordinates).
-pub struct Company<V, R>
+pub struct Company<V, R, B = PersistentBackend>
 where
     V: StaffEntity,
-    R: Num + Copy + Zero + PartialOrd + Div<Output = R> + FromPrimitive + Debug,
+    R: Resource,
+    B: Backend<V::ID>,
 {
     ceo_id: V::ID,
-    staff: HashMap<V::ID, V>,
-    resources: HashMap<V::ID, R>,
-    supervisors: HashMap<V::ID, V::ID>,
-    subordinates: HashMap<V::ID, HashSet<V::ID>>,
+    staff: Members<V, B>,
+    resources: Arc<B::Map<R>>,
+    supervisors: Arc<B::Map<V::ID>>,
+    subordinates: Arc<B::Map<Arc<B::Set>>>,
+    rounding: Rounding,
+    dust: R,
+    minted: R,
+    burned: R,
+    reservations: im::HashMap<ReservationId, Reservation<V::ID, R>>,
+    reserved: im::HashMap<V::ID, R>,
+    next_reservation_id: u64,
+    limits: im::HashMap<V::ID, Limits<R>>,
+    spent: im::HashMap<V::ID, R>,
+    period: u64,
+    approval_policy: Option<ApprovalPolicy<R>>,
+    requests: im::HashMap<RequestId, TransferRequest<V::ID, R>>,
+    next_request_id: u64,
+    structure: StructureRules,
+    order: StaffOrder,
+    compare_ids: Option<CompareIds<V::ID>>,
+    joined: Arc<B::Map<u64>>,
+    next_joined: u64,
 }
 
-impl<
-        V: StaffEntity,
-        R: Num + Copy + Zero + PartialOrd + FromPrimitive + Div<Output = R> + Debug,
-    > Company<V, R>
-{
+impl<V: StaffEntity, R: Resource, B: Backend<V::ID>> Company<V, R, B> {
     /// Creates a new company with the given CEO.
     pub fn new(ceo: V) -> Self {
         Self {
             ceo_id: ceo.get_id(),
-            resources: HashMap::from([(ceo.get_id(), R::zero())]),
-            staff: HashMap::from([(ceo.get_id(), ceo)]),
-            supervisors: HashMap::new(),
-            subordinates: HashMap::new(),
+            resources: Arc::new(map_of(ceo.get_id(), R::zero())),
+            joined: Arc::new(map_of(ceo.get_id(), 0)),
+            staff: Members::new(map_of(ceo.get_id(), Arc::new(ceo))),
+            supervisors: Arc::default(),
+            subordinates: Arc::default(),
+            rounding: Rounding::default(),
+            dust: R::zero(),
+            minted: R::zero(),
+            burned: R::zero(),
+            reservations: im::HashMap::new(),
+            reserved: im::HashMap::new(),
+            next_reservation_id: 0,
+            limits: im::HashMap::new(),
+            spent: im::HashMap::new(),
+            period: 0,
+            approval_policy: None,
+            requests: im::HashMap::new(),
+            next_request_id: 0,
+            structure: StructureRules::default(),
+            order: StaffOrder::default(),
+            compare_ids: None,
+            next_joined: 1,
         }
     }
 
+    /// Sets the [`Rounding`] mode used when resources are split between staff members.
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Returns the [`Rounding`] mode used when resources are split between staff members.
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
     /// Returns a reference to the CEO of the company.
     pub fn ceo(&self) -> Option<&V> {
         self.staff.get(&self.ceo_id)
@@ -57,12 +137,38 @@ impl<
 
     /// Returns a mutable reference to the CEO of the company.
     pub fn ceo_mut(&mut self) -> Option<&mut V> {
-        self.staff.get_mut(&self.ceo_id)
+        let ceo_id = self.ceo_id.clone();
+        self.get_mut(&ceo_id)
+    }
+
+    /// Lists staff members in the order of joining the company, see [`StaffOrder::Insertion`].
+    pub fn with_insertion_order(mut self) -> Self {
+        self.order = StaffOrder::Insertion;
+        self.compare_ids = None;
+        self
     }
 
-    /// Returns a vector of IDs of all staff members in the company.
+    /// Lists staff members in ascending order of their IDs, see [`StaffOrder::Sorted`].
+    pub fn with_sorted_order(mut self) -> Self
+    where
+        V::ID: Ord,
+    {
+        self.order = StaffOrder::Sorted;
+        self.compare_ids = Some(V::ID::cmp);
+        self
+    }
+
+    /// Returns the [`StaffOrder`] of listed staff members.
+    pub fn staff_order(&self) -> StaffOrder {
+        self.order
+    }
+
+    /// Returns a vector of IDs of all staff members in the company, ordered according to
+    /// [`Company::staff_order`].
     pub fn get_all_staff(&self) -> Vec<V::ID> {
-        self.staff.keys().cloned().collect()
+        let mut ids: Vec<V::ID> = self.staff.keys().cloned().collect();
+        self.sort_ids(&mut ids);
+        ids
     }
 
     /// Returns a reference to a staff object by ID.
@@ -88,6 +194,22 @@ impl<
         self.resources.get(staff_id)
     }
 
+    /// Returns the amount of a staff member's resources held by reservations.
+    pub fn reserved(&self, staff_id: &V::ID) -> R {
+        self.reserved.get(staff_id).copied().unwrap_or_else(R::zero)
+    }
+
+    /// Returns the amount of a staff member's resources which can be withdrawn or transferred,
+    /// that is the balance without reserved resources.
+    ///
+    /// # Returns
+    /// - `Some(R)` if the staff member exists.
+    /// - `None` otherwise.
+    pub fn available(&self, staff_id: &V::ID) -> Option<R> {
+        self.resource(staff_id)
+            .map(|res| *res - self.reserved(staff_id))
+    }
+
     /// Returns the supervisor's ID of the given staff member.
     ///
     /// # Returns
@@ -97,9 +219,19 @@ impl<
         self.supervisors.get(staff_id)
     }
 
-    /// Returns the staff's subordiantes
-    pub fn subordinates(&self, staff_id: &V::ID) -> Option<&HashSet<V::ID>> {
-        self.subordinates.get(staff_id)
+    /// Returns the staff's subordinates in the set type of the backend.
+    ///
+    /// Backends keeping subordinates in a [`HashSet`], including the default one, also provide
+    /// [`Company::subordinates`].
+    pub fn subordinate_set(&self, staff_id: &V::ID) -> Option<&B::Set> {
+        self.subordinates.get(staff_id).map(Arc::as_ref)
+    }
+
+    /// Returns the staff's subordinates ordered according to [`Company::staff_order`].
+    pub fn subordinates_in_order(&self, staff_id: &V::ID) -> Option<Vec<V::ID>> {
+        let mut ids: Vec<V::ID> = self.subordinate_set(staff_id)?.iter().cloned().collect();
+        self.sort_ids(&mut ids);
+        Some(ids)
     }
 
     /// Mints (creates) resources and add them to the CEO's balance.
@@ -107,13 +239,14 @@ impl<
     /// # Arguments
     /// - `amount`: The amount of resources to mint.
     pub fn mint(&mut self, amount: R) {
-        self.resources
-            .entry(self.ceo_id.clone())
-            .and_modify(|res| *res = *res + amount)
-            .or_insert(amount);
+        let ceo_id = self.ceo_id.clone();
+        self.credit(&ceo_id, amount);
+        self.minted = self.minted + amount;
     }
 
-    /// Withdraws resources from a staff member's balance.
+    /// Withdraws resources from a staff member's balance, removing them from the company.
+    ///
+    /// Withdrawn resources are counted as burned (see [`Company::total_burned`]).
     ///
     /// # Arguments
     /// - `staff_id`: The ID of the staff member.
@@ -122,27 +255,58 @@ impl<
     /// # Errors
     /// - [`Error::StaffNotFound`] if the staff member does not exist.
     /// - [`Error::InsufficientResourcesError`] if the staff member has insufficient resources.
+    /// - [`Error::LimitExceeded`] if the withdrawal exceeds the staff member's limits.
     pub fn withdraw(&mut self, staff_id: &V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
-        let resource = self
+        self.check_available(staff_id, amount)?;
+        self.check_limits(staff_id, None, amount)?;
+
+        self.debit(staff_id, amount)?;
+        self.record_spending(staff_id, amount);
+        self.burned = self.burned + amount;
+        Ok(())
+    }
+
+    /// Returns the total amount of resources ever minted.
+    pub fn total_minted(&self) -> R {
+        self.minted
+    }
+
+    /// Returns the total amount of resources ever withdrawn (burned).
+    pub fn total_burned(&self) -> R {
+        self.burned
+    }
+
+    /// Returns the circulating supply: minted resources which have not been burned yet.
+    pub fn total_supply(&self) -> R {
+        self.minted - self.burned
+    }
+
+    /// Checks that the balances of all staff members together with the dust account add up to
+    /// the circulating supply.
+    ///
+    /// # Errors
+    /// - [`Error::SupplyMismatch`] if the balances do not reconcile with the supply.
+    pub fn check_supply(&self) -> Result<(), Error<V::ID, R>> {
+        let balances = self
             .resources
-            .get_mut(staff_id)
-            .ok_or(Error::StaffNotFound {
-                staff_id: staff_id.clone(),
-            })?;
+            .values()
+            .fold(self.dust, |acc, res| acc + *res);
 
-        if *resource < amount {
-            return Err(Error::InsufficientResourcesError {
-                staff_id: staff_id.clone(),
-                available: *resource,
-                required: amount,
+        if balances != self.total_supply() {
+            return Err(Error::SupplyMismatch {
+                supply: self.total_supply(),
+                balances,
             });
         }
 
-        *resource = *resource - amount;
         Ok(())
     }
 
-    /// Hires a new staff member under a supervisor and give it a `10%` of the supervisor's resources.
+    /// Hires a new staff member under a supervisor and give it a `10%` of the supervisor's
+    /// available resources.
+    ///
+    /// The share is rounded with the company's [`Rounding`] mode, so its value never leaves
+    /// the supervisor and the hired member.
     ///
     /// # Arguments
     /// - `staff`: The new staff member.
@@ -154,6 +318,8 @@ impl<
     /// # Errors
     /// - [`Error::StaffNotFound`] if the supervisor does not exist.
     /// - [`Error::StaffAlreadyExists`] if the new staff member already exists.
+    /// - [`Error::StructureViolation`] if the supervisor has the maximum number of reports or
+    ///   the new staff member would be placed too deep.
     pub fn hire(&mut self, staff: V, supervisor_id: &V::ID) -> Result<V::ID, Error<V::ID, R>> {
         self.staff_exists(supervisor_id)?;
 
@@ -162,28 +328,32 @@ impl<
                 staff_id: staff.get_id(),
             });
         }
+        self.check_structure(&staff.get_id(), || 0, supervisor_id)?;
 
         let staff_id = staff.get_id();
         self.staff.insert(staff_id.clone(), staff);
-        self.supervisors
+        self.supervisors_mut()
             .insert(staff_id.clone(), supervisor_id.clone());
+        self.join(&staff_id);
 
-        if let Some(s) = self.subordinates.get_mut(supervisor_id) {
-            s.insert(staff_id.clone());
-        } else {
-            self.subordinates
-                .insert(supervisor_id.clone(), HashSet::from([staff_id.clone()]));
-        }
+        Arc::make_mut(self.subordinates_mut().get_or_default(supervisor_id))
+            .insert(staff_id.clone());
 
-        let amount = *self.resource(supervisor_id).unwrap() / R::from_i16(10).unwrap();
-        self.withdraw(supervisor_id, amount)?;
-        self.resources.insert(staff_id.clone(), amount);
+        let amount = self
+            .available(supervisor_id)
+            .unwrap()
+            .div_rounded(R::from_i16(10).unwrap(), self.rounding);
+        self.debit(supervisor_id, amount)?;
+        self.resources_mut().insert(staff_id.clone(), amount);
         Ok(staff_id)
     }
 
     /// Fires a staff member, transferring their resources to the supervisor and removes all data
     /// associated with current member.
     ///
+    /// Reservations of the fired member are passed to the supervisor together with the resources.
+    /// Pending transfer requests sent or received by the fired member are cancelled.
+    ///
     /// # Arguments
     /// - `staff_id`: The ID of the staff member to fire.
     ///
@@ -200,24 +370,57 @@ impl<
             return Err(Error::CannotFireCeo);
         }
 
+        let cancelled: Vec<RequestId> = self
+            .requests
+            .iter()
+            .filter(|(_, request)| request.from == *staff_id || request.to == *staff_id)
+            .map(|(request_id, _)| *request_id)
+            .collect();
+        for request_id in cancelled {
+            let request = self.requests.remove(&request_id).unwrap();
+            self.release(&request.reservation_id)?;
+        }
+        for (_, request) in self.requests.iter_mut() {
+            request.approvals.remove(staff_id);
+        }
+
         let supervisor_id = self.supervisor(staff_id).unwrap().clone();
-        self.supervisors.remove(staff_id);
+        self.supervisors_mut().remove(staff_id);
+        if let Some(subordinates) = self.subordinates_mut().get_mut(&supervisor_id) {
+            Arc::make_mut(subordinates).remove(staff_id);
+        }
+
+        if let Some(reserved) = self.reserved.remove(staff_id) {
+            for (_, reservation) in self.reservations.iter_mut() {
+                if reservation.staff_id == *staff_id {
+                    reservation.staff_id = supervisor_id.clone();
+                }
+            }
+            let total = self.reserved(&supervisor_id) + reserved;
+            self.reserved.insert(supervisor_id.clone(), total);
+        }
 
-        let resource = self.resource(staff_id).unwrap();
-        self.transfer(staff_id, &supervisor_id, *resource)?;
-        self.resources.remove(staff_id);
+        let resource = *self.resource(staff_id).unwrap();
+        self.debit(staff_id, resource)?;
+        self.credit(&supervisor_id, resource);
+        self.resources_mut().remove(staff_id);
+        self.limits.remove(staff_id);
+        self.spent.remove(staff_id);
 
-        if let Some(subordinates) = self.subordinates.remove(staff_id) {
+        if let Some(subordinates) = self.subordinates_mut().remove(staff_id) {
             for id in subordinates.iter() {
-                self.supervisors.insert(id.clone(), supervisor_id.clone());
+                self.supervisors_mut()
+                    .insert(id.clone(), supervisor_id.clone());
             }
 
-            self.subordinates
-                .entry(supervisor_id.clone())
-                .or_default()
-                .extend(subordinates);
+            let supervisor_subordinates =
+                Arc::make_mut(self.subordinates_mut().get_or_default(&supervisor_id));
+            for id in subordinates.iter() {
+                supervisor_subordinates.insert(id.clone());
+            }
         }
 
+        Arc::make_mut(&mut self.joined).remove(staff_id);
         Ok(self.staff.remove(staff_id).unwrap())
     }
 
@@ -230,7 +433,8 @@ impl<
     /// # Errors:
     /// - [`Error::StaffNotFound`] if either staff member does not exist.
     /// - [`crate::errors::Error::HierarchyConflict`] if given `supervisor` is a current
-    /// staff subordinate or in the lists of its subordinates.
+    ///   staff subordinate or in the lists of its subordinates.
+    /// - [`Error::StructureViolation`] if the move breaks one of the [`StructureRules`].
     pub fn change_supervisor(
         &mut self,
         staff_id: &V::ID,
@@ -246,15 +450,14 @@ impl<
         let mut found = false;
         let mut subordinates_id: Vec<&V::ID> = Vec::from([staff_id]);
 
-        'outer: while !subordinates_id.is_empty() {
-            let id = subordinates_id.pop().unwrap();
+        'outer: while let Some(id) = subordinates_id.pop() {
             if id == supervisor_id {
                 found = true;
                 break;
             }
 
-            if let Some(subordinates) = self.subordinates(id) {
-                for id in subordinates {
+            if let Some(subordinates) = self.subordinate_set(id) {
+                for id in subordinates.iter() {
                     if id == supervisor_id {
                         found = true;
                         break 'outer;
@@ -278,24 +481,96 @@ impl<
                 staff_id: staff_id.clone(),
             })?
             .clone();
+        if current_supervisor == *supervisor_id {
+            return Ok(());
+        }
 
+        self.check_structure(staff_id, || self.height(staff_id), supervisor_id)?;
+        if let Some(limit) = self.structure.min_reports {
+            let remaining = self.subordinate_set(&current_supervisor).unwrap().len() - 1;
+            if current_supervisor != self.ceo_id && remaining > 0 && remaining < limit {
+                return Err(Error::StructureViolation {
+                    staff_id: current_supervisor,
+                    rule: StructureRule::MinReports,
+                    limit,
+                    attempted: remaining,
+                });
+            }
+        }
+
+        self.move_staff(staff_id, &current_supervisor, supervisor_id);
+        Ok(())
+    }
+
+    /// Sets the structural rules of the hierarchy replacing the previous ones.
+    ///
+    /// The current hierarchy is not checked, see [`Company::violations`].
+    pub fn set_structure_rules(&mut self, rules: StructureRules) {
+        self.structure = rules;
+    }
+
+    /// Returns the structural rules of the hierarchy.
+    pub fn structure_rules(&self) -> &StructureRules {
+        &self.structure
+    }
+
+    /// Returns violations of the [`StructureRules`] in the current hierarchy, supervisors go
+    /// before their subordinates.
+    pub fn violations(&self) -> Vec<Violation<V::ID>>
+    where
+        V::ID: Ord,
+    {
+        self.tree().violations(&self.structure)
+    }
+
+    /// Suggests supervisor changes bringing the hierarchy within the [`StructureRules`] without
+    /// applying them.
+    ///
+    /// Too deep staff members are lifted to the deepest allowed level of their chain of command,
+    /// excess reports are passed to their siblings or to the highest supervisor with free
+    /// capacity, and reports of managers with too few of them go to the manager's supervisor.
+    /// Moves are planned on the whole hierarchy in order, so a later move may depend on an
+    /// earlier one. Violations which can not be fixed this way are left in place.
+    ///
+    /// # Returns
+    /// - The moves in the order they have to be applied.
+    pub fn rebalance_plan(&self) -> Vec<Move<V::ID>>
+    where
+        V::ID: Ord,
+    {
+        self.tree().rebalance(&self.structure)
+    }
+
+    /// Applies the moves suggested by [`Company::rebalance_plan`].
+    ///
+    /// Staff members keep their resources, only supervisors change.
+    ///
+    /// # Returns
+    /// - The applied moves.
+    pub fn rebalance(&mut self) -> Vec<Move<V::ID>>
+    where
+        V::ID: Ord,
+    {
+        let moves = self.rebalance_plan();
+        for step in moves.iter() {
+            self.move_staff(&step.staff_id, &step.from, &step.to);
+        }
+        moves
+    }
+
+    /// Moves a staff member under a new supervisor without any checks.
+    fn move_staff(&mut self, staff_id: &V::ID, current_supervisor: &V::ID, supervisor_id: &V::ID) {
         // Remove from previours supervisor's subordinates list
-        if let Some(subordinates) = self.subordinates.get_mut(&current_supervisor) {
-            subordinates.remove(staff_id);
+        if let Some(subordinates) = self.subordinates_mut().get_mut(current_supervisor) {
+            Arc::make_mut(subordinates).remove(staff_id);
         }
 
         // Add to the new supervisor's subordinates list
-        if let Some(subordinates) = self.subordinates.get_mut(&supervisor_id) {
-            subordinates.insert(staff_id.clone());
-        } else {
-            self.subordinates
-                .insert(supervisor_id.clone(), HashSet::from([staff_id.clone()]));
-        }
+        Arc::make_mut(self.subordinates_mut().get_or_default(supervisor_id))
+            .insert(staff_id.clone());
 
         // update in global map
-        *self.supervisors.get_mut(staff_id).unwrap() = supervisor_id.clone();
-
-        Ok(())
+        *self.supervisors_mut().get_mut(staff_id).unwrap() = supervisor_id.clone();
     }
 
     /// Transfers resources from one staff member to another.
@@ -308,6 +583,8 @@ impl<
     /// # Errors
     /// - [`Error::StaffNotFound`] if either staff member does not exist.
     /// - [`Error::InsufficientResourcesError`] if the sender has insufficient resources.
+    /// - [`Error::LimitExceeded`] if the transfer exceeds the sender's or the receiver's limits.
+    /// - [`Error::ApprovalRequired`] if the amount is above the approval threshold.
     pub fn transfer(&mut self, from: &V::ID, to: &V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
         if !self.staff.contains_key(from) {
             return Err(Error::StaffNotFound {
@@ -321,14 +598,697 @@ impl<
             });
         }
 
-        self.withdraw(from, amount)?;
-        self.resources
-            .entry(to.clone())
-            .and_modify(|res| *res = *res + amount)
-            .or_insert(amount);
+        self.check_approval(from, amount)?;
+        self.execute_transfer(from, to, amount)
+    }
+
+    /// Splits `amount` of the sender's resources between `recipients` proportionally to their
+    /// weights.
+    ///
+    /// Each share is rounded with the company's [`Rounding`] mode and whatever is left over is
+    /// added to the company's dust account (see [`Company::dust`]), so no resources are lost.
+    ///
+    /// # Arguments
+    /// - `from`: The ID of the sender.
+    /// - `recipients`: Pairs of receiver IDs and their weights.
+    /// - `amount`: The total amount of resources to distribute.
+    ///
+    /// # Returns
+    /// - The amount added to the dust account.
+    ///
+    /// The distribution is a single payment of `amount` by the sender, so it is checked against
+    /// the approval threshold and the sender's limits like [`Company::transfer`], and each
+    /// receiver's share against its balance limit.
+    ///
+    /// # Errors
+    /// - [`Error::StaffNotFound`] if the sender or any receiver does not exist.
+    /// - [`Error::InsufficientResourcesError`] if the sender has insufficient resources.
+    /// - [`Error::LimitExceeded`] if the payment exceeds the sender's or a receiver's limits.
+    /// - [`Error::ApprovalRequired`] if the amount is above the approval threshold.
+    pub fn distribute(
+        &mut self,
+        from: &V::ID,
+        recipients: &[(V::ID, R)],
+        amount: R,
+    ) -> Result<R, Error<V::ID, R>> {
+        self.staff_exists(from)?;
+        for (id, _) in recipients {
+            self.staff_exists(id)?;
+        }
+
+        self.check_approval(from, amount)?;
+        self.check_available(from, amount)?;
+        self.check_limits(from, None, amount)?;
+
+        let weights: Vec<R> = recipients.iter().map(|(_, weight)| *weight).collect();
+        let allocation = allocate(amount, &weights, self.rounding);
+        let mut received: HashMap<&V::ID, R> = HashMap::new();
+        for ((id, _), share) in recipients.iter().zip(&allocation.shares) {
+            let total = received.entry(id).or_insert_with(R::zero);
+            *total = *total + *share;
+        }
+        for (id, total) in received {
+            self.check_balance_limit(id, total)?;
+        }
+
+        self.debit(from, amount)?;
+        for ((id, _), share) in recipients.iter().zip(allocation.shares) {
+            self.credit(id, share);
+        }
+        self.record_spending(from, amount);
+
+        self.dust = self.dust + allocation.dust;
+        Ok(allocation.dust)
+    }
+
+    /// Returns the resources accumulated in the company's dust account.
+    pub fn dust(&self) -> R {
+        self.dust
+    }
+
+    /// Moves all resources from the dust account to a staff member's balance.
+    ///
+    /// # Returns
+    /// - The amount moved.
+    ///
+    /// # Errors
+    /// - [`Error::StaffNotFound`] if the staff member does not exist.
+    pub fn sweep_dust(&mut self, to: &V::ID) -> Result<R, Error<V::ID, R>> {
+        self.staff_exists(to)?;
+
+        let amount = self.dust;
+        self.dust = R::zero();
+        self.credit(to, amount);
+        Ok(amount)
+    }
+
+    /// Reserves part of a staff member's balance for a pending payment.
+    ///
+    /// Reserved resources stay on the balance but are excluded from the amount available to
+    /// [`Company::withdraw`] and [`Company::transfer`].
+    ///
+    /// # Arguments
+    /// - `staff_id`: The ID of the staff member.
+    /// - `amount`: The amount to reserve.
+    ///
+    /// # Returns
+    /// - The ID of the new reservation.
+    ///
+    /// # Errors
+    /// - [`Error::StaffNotFound`] if the staff member does not exist.
+    /// - [`Error::InsufficientResourcesError`] if the staff member has insufficient available
+    ///   resources.
+    pub fn reserve(
+        &mut self,
+        staff_id: &V::ID,
+        amount: R,
+    ) -> Result<ReservationId, Error<V::ID, R>> {
+        self.check_available(staff_id, amount)?;
+
+        let reservation_id = ReservationId(self.next_reservation_id);
+        self.next_reservation_id += 1;
+
+        let total = self.reserved(staff_id) + amount;
+        self.reserved.insert(staff_id.clone(), total);
+        self.reservations.insert(
+            reservation_id,
+            Reservation {
+                staff_id: staff_id.clone(),
+                amount,
+            },
+        );
+        Ok(reservation_id)
+    }
+
+    /// Returns an active reservation by ID.
+    pub fn reservation(&self, reservation_id: &ReservationId) -> Option<&Reservation<V::ID, R>> {
+        self.reservations.get(reservation_id)
+    }
+
+    /// Cancels a reservation making its resources available to the staff member again.
+    ///
+    /// # Returns
+    /// - The released reservation.
+    ///
+    /// # Errors
+    /// - [`Error::ReservationNotFound`] if the reservation does not exist.
+    pub fn release(
+        &mut self,
+        reservation_id: &ReservationId,
+    ) -> Result<Reservation<V::ID, R>, Error<V::ID, R>> {
+        let reservation =
+            self.reservations
+                .remove(reservation_id)
+                .ok_or(Error::ReservationNotFound {
+                    reservation_id: *reservation_id,
+                })?;
+
+        let total = self.reserved(&reservation.staff_id) - reservation.amount;
+        if total.is_zero() {
+            self.reserved.remove(&reservation.staff_id);
+        } else {
+            self.reserved.insert(reservation.staff_id.clone(), total);
+        }
+        Ok(reservation)
+    }
+
+    /// Completes a reservation transferring the reserved resources to another staff member.
+    ///
+    /// # Arguments
+    /// - `reservation_id`: The ID of the reservation.
+    /// - `to`: The ID of the receiver.
+    ///
+    /// # Errors
+    /// - [`Error::ReservationNotFound`] if the reservation does not exist.
+    /// - [`Error::StaffNotFound`] if the receiver does not exist.
+    /// - [`Error::LimitExceeded`] if the transfer exceeds the owner's or the receiver's limits.
+    /// - [`Error::ApprovalRequired`] if the amount is above the approval threshold.
+    pub fn commit(
+        &mut self,
+        reservation_id: &ReservationId,
+        to: &V::ID,
+    ) -> Result<(), Error<V::ID, R>> {
+        let reservation =
+            self.reservations
+                .get(reservation_id)
+                .ok_or(Error::ReservationNotFound {
+                    reservation_id: *reservation_id,
+                })?;
+        self.check_approval(&reservation.staff_id, reservation.amount)?;
+        self.commit_reservation(reservation_id, to)
+    }
+
+    /// Sets spending limits of a staff member replacing the previous ones.
+    ///
+    /// # Errors
+    /// - [`Error::StaffNotFound`] if the staff member does not exist.
+    pub fn set_limits(
+        &mut self,
+        staff_id: &V::ID,
+        limits: Limits<R>,
+    ) -> Result<(), Error<V::ID, R>> {
+        self.staff_exists(staff_id)?;
+        self.limits.insert(staff_id.clone(), limits);
         Ok(())
     }
 
+    /// Removes own spending limits of a staff member.
+    ///
+    /// # Returns
+    /// - `Some(Limits)` if the staff member had own limits.
+    /// - `None` otherwise.
+    pub fn remove_limits(&mut self, staff_id: &V::ID) -> Option<Limits<R>> {
+        self.limits.remove(staff_id)
+    }
+
+    /// Returns the limits applied to a staff member: its own limits or, if there are none, the
+    /// limits of the closest supervisor which has limits and allows inheriting them.
+    pub fn effective_limits(&self, staff_id: &V::ID) -> Option<&Limits<R>> {
+        if let Some(limits) = self.limits.get(staff_id) {
+            return Some(limits);
+        }
+
+        let mut current = staff_id;
+        while let Some(supervisor_id) = self.supervisor(current) {
+            if let Some(limits) = self.limits.get(supervisor_id) {
+                return limits.inherit.then_some(limits);
+            }
+            current = supervisor_id;
+        }
+        None
+    }
+
+    /// Returns the amount transferred and withdrawn by a staff member during the current period.
+    pub fn spent(&self, staff_id: &V::ID) -> R {
+        self.spent.get(staff_id).copied().unwrap_or_else(R::zero)
+    }
+
+    /// Returns the number of the current spending period.
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// Starts a new spending period resetting the amounts spent by all staff members.
+    pub fn advance_period(&mut self) {
+        self.period += 1;
+        self.spent.clear();
+    }
+
+    /// Sets the [`ApprovalPolicy`] for large transfers, `None` disables approvals.
+    pub fn set_approval_policy(&mut self, policy: Option<ApprovalPolicy<R>>) {
+        self.approval_policy = policy;
+    }
+
+    /// Returns the [`ApprovalPolicy`] for large transfers.
+    pub fn approval_policy(&self) -> Option<&ApprovalPolicy<R>> {
+        self.approval_policy.as_ref()
+    }
+
+    /// Creates a transfer request which is executed once enough supervisors of the sender
+    /// approve it.
+    ///
+    /// The amount is reserved on the sender's balance until the request is executed, rejected or
+    /// cancelled.
+    ///
+    /// # Arguments
+    /// - `from`: The ID of the sender.
+    /// - `to`: The ID of the receiver.
+    /// - `amount`: The amount of resources to transfer.
+    ///
+    /// # Returns
+    /// - The ID of the new request.
+    ///
+    /// # Errors
+    /// - [`Error::StaffNotFound`] if either staff member does not exist.
+    /// - [`Error::ApprovalNotRequired`] if the sender has no supervisors to approve the request.
+    /// - [`Error::InsufficientResourcesError`] if the sender has insufficient resources.
+    pub fn request_transfer(
+        &mut self,
+        from: &V::ID,
+        to: &V::ID,
+        amount: R,
+    ) -> Result<RequestId, Error<V::ID, R>> {
+        self.staff_exists(from)?;
+        self.staff_exists(to)?;
+        if self.required_approvals(from) == 0 {
+            return Err(Error::ApprovalNotRequired {
+                staff_id: from.clone(),
+            });
+        }
+
+        let reservation_id = self.reserve(from, amount)?;
+        let request_id = RequestId(self.next_request_id);
+        self.next_request_id += 1;
+
+        self.requests.insert(
+            request_id,
+            TransferRequest {
+                from: from.clone(),
+                to: to.clone(),
+                amount,
+                reservation_id,
+                approvals: HashSet::new(),
+            },
+        );
+        Ok(request_id)
+    }
+
+    /// Returns a pending transfer request by ID.
+    pub fn transfer_request(&self, request_id: &RequestId) -> Option<&TransferRequest<V::ID, R>> {
+        self.requests.get(request_id)
+    }
+
+    /// Approves a transfer request and executes the transfer once enough approvals are
+    /// collected.
+    ///
+    /// Repeated approvals by the same supervisor are counted once. If the execution fails the
+    /// request stays pending and the next approval retries it.
+    ///
+    /// # Arguments
+    /// - `request_id`: The ID of the request.
+    /// - `approver_id`: The ID of a supervisor up the sender's chain of command.
+    ///
+    /// # Errors
+    /// - [`Error::RequestNotFound`] if the request does not exist.
+    /// - [`Error::StaffHasNoPermission`] if the approver is not a supervisor of the sender.
+    /// - Errors of [`Company::transfer`] if the transfer can not be executed.
+    pub fn approve(
+        &mut self,
+        request_id: &RequestId,
+        approver_id: &V::ID,
+    ) -> Result<ApprovalStatus, Error<V::ID, R>> {
+        self.check_approver(request_id, approver_id)?;
+
+        let from = self.requests[request_id].from.clone();
+        let required = self.required_approvals(&from);
+
+        let request = self.requests.get_mut(request_id).unwrap();
+        request.approvals.insert(approver_id.clone());
+        let approvals = request.approvals.len();
+        if approvals < required {
+            return Ok(ApprovalStatus::Pending {
+                approvals,
+                required,
+            });
+        }
+
+        let request = self.requests.get(request_id).unwrap();
+        let (reservation_id, to) = (request.reservation_id, request.to.clone());
+        self.commit_reservation(&reservation_id, &to)?;
+        self.requests.remove(request_id);
+        Ok(ApprovalStatus::Executed)
+    }
+
+    /// Rejects a transfer request releasing the reserved resources.
+    ///
+    /// # Returns
+    /// - The rejected request.
+    ///
+    /// # Errors
+    /// - [`Error::RequestNotFound`] if the request does not exist.
+    /// - [`Error::StaffHasNoPermission`] if the approver is not a supervisor of the sender.
+    pub fn reject(
+        &mut self,
+        request_id: &RequestId,
+        approver_id: &V::ID,
+    ) -> Result<TransferRequest<V::ID, R>, Error<V::ID, R>> {
+        self.check_approver(request_id, approver_id)?;
+
+        let request = self.requests.remove(request_id).unwrap();
+        self.release(&request.reservation_id)?;
+        Ok(request)
+    }
+
+    /// Cancels a transfer request of the sender releasing the reserved resources.
+    ///
+    /// # Returns
+    /// - The cancelled request.
+    ///
+    /// # Errors
+    /// - [`Error::RequestNotFound`] if the request does not exist.
+    /// - [`Error::StaffHasNoPermission`] if the staff member is not the sender of the request.
+    pub fn cancel_request(
+        &mut self,
+        request_id: &RequestId,
+        staff_id: &V::ID,
+    ) -> Result<TransferRequest<V::ID, R>, Error<V::ID, R>> {
+        let request = self
+            .requests
+            .get(request_id)
+            .ok_or(Error::RequestNotFound {
+                request_id: *request_id,
+            })?;
+        if request.from != *staff_id {
+            return Err(Error::StaffHasNoPermission {
+                staff_id: staff_id.clone(),
+            });
+        }
+
+        let request = self.requests.remove(request_id).unwrap();
+        self.release(&request.reservation_id)?;
+        Ok(request)
+    }
+
+    /// Runs a payroll round: walks the hierarchy top-down from the CEO and makes every
+    /// supervisor pay its direct subordinates according to `rule`.
+    ///
+    /// Subordinates are paid after their supervisor, so resources received during the round are
+    /// passed further down. Every supervisor pays its subordinates in the order of
+    /// [`Company::subordinates_in_order`], or in the order they joined if the company is
+    /// [`StaffOrder::Unordered`]. Payments a supervisor can not afford are skipped and reported as
+    /// shortfalls instead of failing the whole round. Payroll is not a spending, so limits and
+    /// approvals are not applied.
+    ///
+    /// # Returns
+    /// - The [`PayrollReport`] with all payouts and shortfalls.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPercentage`] if the percentage of the rule is not between `0` and `100`.
+    pub fn run_payroll(
+        &mut self,
+        rule: &PayrollRule<V, R>,
+    ) -> Result<PayrollReport<V::ID, R>, Error<V::ID, R>> {
+        if let PayrollRule::Percentage(percentage) | PayrollRule::Weighted { percentage, .. } = rule
+        {
+            check_percentage(*percentage)?;
+        }
+
+        let mut report = PayrollReport {
+            payouts: Vec::new(),
+            shortfalls: Vec::new(),
+            dust: R::zero(),
+        };
+
+        let mut queue = VecDeque::from([self.ceo_id.clone()]);
+        while let Some(supervisor_id) = queue.pop_front() {
+            let subordinates = self.payout_order(&supervisor_id);
+            if subordinates.is_empty() {
+                continue;
+            }
+            queue.extend(subordinates.iter().cloned());
+
+            let available = self.available(&supervisor_id).unwrap();
+            let amounts = match rule {
+                PayrollRule::FixedSalary(salary) => vec![*salary; subordinates.len()],
+                PayrollRule::Percentage(percentage) | PayrollRule::Weighted { percentage, .. } => {
+                    let pool = available.percent_of(*percentage, self.rounding);
+                    let weights: Vec<R> = match rule {
+                        PayrollRule::Weighted { weight, .. } => subordinates
+                            .iter()
+                            .map(|id| weight(self.get(id).unwrap()))
+                            .collect(),
+                        _ => vec![R::one(); subordinates.len()],
+                    };
+
+                    let allocation = allocate(pool, &weights, self.rounding);
+                    self.debit(&supervisor_id, allocation.dust)?;
+                    self.dust = self.dust + allocation.dust;
+                    report.dust = report.dust + allocation.dust;
+                    allocation.shares
+                }
+            };
+
+            for (id, amount) in subordinates.into_iter().zip(amounts) {
+                let available = self.available(&supervisor_id).unwrap();
+                if available < amount {
+                    report.shortfalls.push(Shortfall {
+                        from: supervisor_id.clone(),
+                        to: id,
+                        required: amount,
+                        available,
+                    });
+                    continue;
+                }
+
+                self.debit(&supervisor_id, amount)?;
+                self.credit(&id, amount);
+                report.payouts.push(Payout {
+                    from: supervisor_id.clone(),
+                    to: id,
+                    amount,
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Mints resources and splits them through the whole hierarchy in one pass.
+    ///
+    /// Starting from the CEO every supervisor keeps [`CascadeRule::keep_percentage`] of what it
+    /// receives and passes the rest to its direct subordinates, weighted by
+    /// [`CascadeRule::weights`]. Subordinates are credited in the same order as by
+    /// [`Company::run_payroll`]. Only the minted amount is cascaded, existing balances are not
+    /// touched. Rounding leftovers are added to the dust account.
+    ///
+    /// # Arguments
+    /// - `amount`: The amount of resources to mint.
+    /// - `rule`: The [`CascadeRule`] of splitting.
+    ///
+    /// # Returns
+    /// - The [`CascadeReport`] with amounts credited to every staff member.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidPercentage`] if [`CascadeRule::keep_percentage`] is not between `0` and
+    ///   `100`.
+    pub fn mint_and_cascade(
+        &mut self,
+        amount: R,
+        rule: &CascadeRule<R>,
+    ) -> Result<CascadeReport<V::ID, R>, Error<V::ID, R>> {
+        check_percentage(rule.keep_percentage)?;
+
+        let mut report = CascadeReport {
+            credited: Vec::new(),
+            dust: R::zero(),
+        };
+        let sizes = match rule.weights {
+            CascadeWeights::Equal => HashMap::new(),
+            CascadeWeights::SubtreeSize => self.subtree_sizes(),
+        };
+
+        self.minted = self.minted + amount;
+
+        let mut queue = VecDeque::from([(self.ceo_id.clone(), amount)]);
+        while let Some((staff_id, received)) = queue.pop_front() {
+            let subordinates = self.payout_order(&staff_id);
+
+            let kept = if subordinates.is_empty() {
+                received
+            } else {
+                received.percent_of(rule.keep_percentage, self.rounding)
+            };
+            self.credit(&staff_id, kept);
+            report.credited.push((staff_id, kept));
+
+            if subordinates.is_empty() {
+                continue;
+            }
+
+            let weights: Vec<R> = subordinates
+                .iter()
+                .map(|id| match rule.weights {
+                    CascadeWeights::Equal => R::one(),
+                    CascadeWeights::SubtreeSize => R::from_usize(sizes[id]).unwrap(),
+                })
+                .collect();
+            let allocation = allocate(received - kept, &weights, self.rounding);
+            self.dust = self.dust + allocation.dust;
+            report.dust = report.dust + allocation.dust;
+            queue.extend(subordinates.into_iter().zip(allocation.shares));
+        }
+
+        Ok(report)
+    }
+
+    /// Sweeps resources of all direct and indirect subordinates of `root_id` up the hierarchy.
+    ///
+    /// Every subordinate gives [`SweepRule::amount`] of its available resources to the target
+    /// of [`SweepRule::target`]. Amounts are computed from the balances before the sweep, so with
+    /// [`SweepTarget::Supervisor`] resources move exactly one level up. Limits and approvals are
+    /// not applied.
+    ///
+    /// # Arguments
+    /// - `root_id`: The ID of the staff member whose subtree is swept.
+    /// - `rule`: The [`SweepRule`].
+    ///
+    /// # Returns
+    /// - The [`SweepReport`] with all moved amounts.
+    ///
+    /// # Errors
+    /// - [`Error::StaffNotFound`] if the root staff member does not exist.
+    /// - [`Error::InvalidPercentage`] if [`SweepAmount::Percentage`] is not between `0` and `100`.
+    pub fn sweep_to(
+        &mut self,
+        root_id: &V::ID,
+        rule: &SweepRule<R>,
+    ) -> Result<SweepReport<V::ID, R>, Error<V::ID, R>> {
+        self.staff_exists(root_id)?;
+        if let SweepAmount::Percentage(percentage) = rule.amount {
+            check_percentage(percentage)?;
+        }
+
+        let swept: Vec<Sweep<V::ID, R>> = self
+            .subtree(root_id)
+            .into_iter()
+            .skip(1)
+            .filter_map(|staff_id| {
+                let available = self.available(&staff_id).unwrap();
+                let amount = match rule.amount {
+                    SweepAmount::All => available,
+                    SweepAmount::AboveFloor(floor) if available > floor => available - floor,
+                    SweepAmount::AboveFloor(_) => R::zero(),
+                    SweepAmount::Percentage(percentage) => {
+                        available.percent_of(percentage, self.rounding)
+                    }
+                };
+                let amount = if amount > available {
+                    available
+                } else {
+                    amount
+                };
+                let to = match rule.target {
+                    SweepTarget::Root => root_id.clone(),
+                    SweepTarget::Supervisor => self.supervisor(&staff_id).unwrap().clone(),
+                };
+
+                (!amount.is_zero()).then_some(Sweep {
+                    from: staff_id,
+                    to,
+                    amount,
+                })
+            })
+            .collect();
+
+        let mut total = R::zero();
+        for sweep in swept.iter() {
+            self.debit(&sweep.from, sweep.amount)?;
+            self.credit(&sweep.to, sweep.amount);
+            total = total + sweep.amount;
+        }
+
+        Ok(SweepReport { swept, total })
+    }
+
+    /// Computes organisation statistics: depth of the hierarchy, span of control and distribution
+    /// of staff balances.
+    ///
+    /// # Returns
+    /// - The [`CompanyStats`] of the company.
+    pub fn stats(&self) -> CompanyStats<V::ID, R>
+    where
+        R: ToPrimitive,
+    {
+        let mut depths = HashMap::from([(self.ceo_id.clone(), 0)]);
+        let mut span_of_control = BTreeMap::new();
+        let mut single_report_managers = Vec::new();
+        let mut leaves = 0;
+
+        for staff_id in self.subtree(&self.ceo_id) {
+            let depth = depths[&staff_id];
+            let subordinates = self.subordinate_set(&staff_id).map_or(0, |ids| ids.len());
+            match subordinates {
+                0 => leaves += 1,
+                span => *span_of_control.entry(span).or_insert(0) += 1,
+            }
+            if subordinates == 1 {
+                single_report_managers.push(staff_id.clone());
+            }
+            for subordinate_id in self
+                .subordinate_set(&staff_id)
+                .into_iter()
+                .flat_map(StaffSet::iter)
+            {
+                depths.insert(subordinate_id.clone(), depth + 1);
+            }
+        }
+
+        let headcount = depths.len();
+        let max_depth = depths.values().copied().max().unwrap_or(0);
+        let average_depth = depths.values().sum::<usize>() as f64 / headcount as f64;
+
+        let mut balances: Vec<R> = self.resources.values().copied().collect();
+        balances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let middle = balances.len() / 2;
+        let median = if balances.len().is_multiple_of(2) {
+            let (low, high) = (balances[middle - 1], balances[middle]);
+            low + (high - low).div_rounded(R::one() + R::one(), self.rounding)
+        } else {
+            balances[middle]
+        };
+
+        // Gini coefficient of the sorted balances: sum((2i - n - 1) * x_i) / (n * sum(x_i)).
+        let values: Vec<f64> = balances.iter().map(|b| b.to_f64().unwrap_or(0.0)).collect();
+        let sum: f64 = values.iter().sum();
+        let n = values.len() as f64;
+        let gini = if sum > 0.0 {
+            let weighted: f64 = values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (2.0 * (i + 1) as f64 - n - 1.0) * value)
+                .sum();
+            weighted / (n * sum)
+        } else {
+            0.0
+        };
+
+        CompanyStats {
+            headcount,
+            max_depth,
+            average_depth,
+            span_of_control,
+            leaves,
+            balances: BalanceStats {
+                min: balances[0],
+                max: balances[balances.len() - 1],
+                median,
+                gini,
+            },
+            single_report_managers,
+        }
+    }
+
     /// private methods
     fn staff_exists(&self, staff_id: &V::ID) -> Result<(), Error<V::ID, R>> {
         self.get(staff_id).ok_or(Error::StaffNotFound {
@@ -336,4 +1296,619 @@ impl<
         })?;
         Ok(())
     }
+
+    /// Returns IDs of the staff member and all its direct and indirect subordinates, supervisors
+    /// go before their subordinates.
+    fn subtree(&self, root_id: &V::ID) -> Vec<V::ID> {
+        let mut order = vec![root_id.clone()];
+        let mut index = 0;
+        while index < order.len() {
+            if let Some(subordinates) = self.subordinate_set(&order[index]) {
+                order.extend(subordinates.iter().cloned());
+            }
+            index += 1;
+        }
+        order
+    }
+
+    fn tree(&self) -> Tree<V::ID>
+    where
+        V::ID: Ord,
+    {
+        Tree::new(self.ceo_id.clone(), self.supervisors.iter())
+    }
+
+    /// Returns the number of levels between the staff member and the CEO.
+    fn depth(&self, staff_id: &V::ID) -> usize {
+        let mut depth = 0;
+        let mut current = staff_id;
+        while let Some(supervisor_id) = self.supervisor(current) {
+            depth += 1;
+            current = supervisor_id;
+        }
+        depth
+    }
+
+    /// Returns the number of levels of subordinates below the staff member, visiting one level
+    /// at a time.
+    fn height(&self, staff_id: &V::ID) -> usize {
+        let mut height = 0;
+        let mut level = vec![staff_id];
+        loop {
+            level = level
+                .into_iter()
+                .filter_map(|id| self.subordinate_set(id))
+                .flat_map(StaffSet::iter)
+                .collect();
+            if level.is_empty() {
+                return height;
+            }
+            height += 1;
+        }
+    }
+
+    /// Checks that the staff member with `height` levels of subordinates can be placed under
+    /// the supervisor. The height is only computed if the depth of the hierarchy is limited.
+    fn check_structure(
+        &self,
+        staff_id: &V::ID,
+        height: impl FnOnce() -> usize,
+        supervisor_id: &V::ID,
+    ) -> Result<(), Error<V::ID, R>> {
+        if let Some(limit) = self.structure.max_reports {
+            let reports = self
+                .subordinate_set(supervisor_id)
+                .map_or(0, |ids| ids.len())
+                + 1;
+            if reports > limit {
+                return Err(Error::StructureViolation {
+                    staff_id: supervisor_id.clone(),
+                    rule: StructureRule::MaxReports,
+                    limit,
+                    attempted: reports,
+                });
+            }
+        }
+
+        if let Some(limit) = self.structure.max_depth {
+            let depth = self.depth(supervisor_id) + 1 + height();
+            if depth > limit {
+                return Err(Error::StructureViolation {
+                    staff_id: staff_id.clone(),
+                    rule: StructureRule::MaxDepth,
+                    limit,
+                    attempted: depth,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn subtree_sizes(&self) -> HashMap<V::ID, usize> {
+        let mut sizes = HashMap::new();
+        for staff_id in self.subtree(&self.ceo_id).into_iter().rev() {
+            let size = 1 + self
+                .subordinate_set(&staff_id)
+                .map_or(0, |ids| ids.iter().map(|id| sizes[id]).sum());
+            sizes.insert(staff_id, size);
+        }
+        sizes
+    }
+
+    fn check_available(&self, staff_id: &V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        let available = self.available(staff_id).ok_or(Error::StaffNotFound {
+            staff_id: staff_id.clone(),
+        })?;
+
+        if available < amount {
+            return Err(Error::InsufficientResourcesError {
+                staff_id: staff_id.clone(),
+                available,
+                required: amount,
+                reserved: self.reserved(staff_id),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn required_approvals(&self, staff_id: &V::ID) -> usize {
+        let required = self
+            .approval_policy
+            .map_or(0, |policy| policy.required_approvals);
+
+        let mut supervisors = 0;
+        let mut current = staff_id;
+        while let Some(supervisor_id) = self.supervisor(current) {
+            if supervisors == required {
+                break;
+            }
+            supervisors += 1;
+            current = supervisor_id;
+        }
+        supervisors
+    }
+
+    fn check_approval(&self, from: &V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        if let Some(policy) = self.approval_policy {
+            if amount > policy.threshold && self.required_approvals(from) > 0 {
+                return Err(Error::ApprovalRequired {
+                    staff_id: from.clone(),
+                    threshold: policy.threshold,
+                    amount,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_approver(
+        &self,
+        request_id: &RequestId,
+        approver_id: &V::ID,
+    ) -> Result<(), Error<V::ID, R>> {
+        let request = self
+            .requests
+            .get(request_id)
+            .ok_or(Error::RequestNotFound {
+                request_id: *request_id,
+            })?;
+
+        let mut current = &request.from;
+        while let Some(supervisor_id) = self.supervisor(current) {
+            if supervisor_id == approver_id {
+                return Ok(());
+            }
+            current = supervisor_id;
+        }
+
+        Err(Error::StaffHasNoPermission {
+            staff_id: approver_id.clone(),
+        })
+    }
+
+    fn commit_reservation(
+        &mut self,
+        reservation_id: &ReservationId,
+        to: &V::ID,
+    ) -> Result<(), Error<V::ID, R>> {
+        let reservation =
+            self.reservations
+                .get(reservation_id)
+                .ok_or(Error::ReservationNotFound {
+                    reservation_id: *reservation_id,
+                })?;
+        self.staff_exists(to)?;
+        self.check_limits(&reservation.staff_id, Some(to), reservation.amount)?;
+
+        let reservation = self.release(reservation_id)?;
+        self.execute_transfer(&reservation.staff_id, to, reservation.amount)
+    }
+
+    fn execute_transfer(
+        &mut self,
+        from: &V::ID,
+        to: &V::ID,
+        amount: R,
+    ) -> Result<(), Error<V::ID, R>> {
+        self.check_available(from, amount)?;
+        self.check_limits(from, Some(to), amount)?;
+
+        self.debit(from, amount)?;
+        self.credit(to, amount);
+        self.record_spending(from, amount);
+        Ok(())
+    }
+
+    fn check_limits(
+        &self,
+        from: &V::ID,
+        to: Option<&V::ID>,
+        amount: R,
+    ) -> Result<(), Error<V::ID, R>> {
+        let exceeded = |staff_id: &V::ID, kind, limit, attempted| Error::LimitExceeded {
+            staff_id: staff_id.clone(),
+            kind,
+            limit,
+            attempted,
+        };
+
+        if let Some(limits) = self.effective_limits(from) {
+            if let Some(limit) = limits.max_transfer {
+                if amount > limit {
+                    return Err(exceeded(from, LimitKind::MaxTransfer, limit, amount));
+                }
+            }
+
+            if let Some(limit) = limits.period_spending {
+                let attempted = self.spent(from) + amount;
+                if attempted > limit {
+                    return Err(exceeded(from, LimitKind::PeriodSpending, limit, attempted));
+                }
+            }
+        }
+
+        match to {
+            Some(to) => self.check_balance_limit(to, amount),
+            None => Ok(()),
+        }
+    }
+
+    fn check_balance_limit(&self, staff_id: &V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        if let Some(limit) = self
+            .effective_limits(staff_id)
+            .and_then(|limits| limits.max_balance)
+        {
+            let attempted = *self.resource(staff_id).unwrap() + amount;
+            if attempted > limit {
+                return Err(Error::LimitExceeded {
+                    staff_id: staff_id.clone(),
+                    kind: LimitKind::MaxBalance,
+                    limit,
+                    attempted,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn record_spending(&mut self, staff_id: &V::ID, amount: R) {
+        let total = self.spent(staff_id) + amount;
+        self.spent.insert(staff_id.clone(), total);
+    }
+
+    /// Adds a staff member with the given balance under an existing supervisor, the balance is
+    /// counted as minted. Used when a company is loaded from storage.
+    pub(crate) fn insert_staff(&mut self, staff: V, supervisor_id: &V::ID, balance: R) {
+        let staff_id = staff.get_id();
+        self.staff.insert(staff_id.clone(), staff);
+        self.supervisors_mut()
+            .insert(staff_id.clone(), supervisor_id.clone());
+        self.join(&staff_id);
+        Arc::make_mut(self.subordinates_mut().get_or_default(supervisor_id))
+            .insert(staff_id.clone());
+        self.credit(&staff_id, balance);
+        self.minted = self.minted + balance;
+    }
+
+    /// Replaces the supply counters and the dust account. Used when a company is loaded from
+    /// storage.
+    #[cfg_attr(not(feature = "persistence"), allow(dead_code))]
+    pub(crate) fn set_supply(&mut self, minted: R, burned: R, dust: R) {
+        self.minted = minted;
+        self.burned = burned;
+        self.dust = dust;
+    }
+
+    /// Returns whether the company carries state which storage does not persist: reservations,
+    /// pending requests, limits, an approval policy, structural rules or a staff order.
+    #[cfg_attr(not(feature = "persistence"), allow(dead_code))]
+    pub(crate) fn has_unpersisted_state(&self) -> bool {
+        !self.reservations.is_empty()
+            || !self.requests.is_empty()
+            || !self.limits.is_empty()
+            || self.approval_policy.is_some()
+            || self.structure != StructureRules::default()
+            || self.order != StaffOrder::Unordered
+    }
+
+    fn join(&mut self, staff_id: &V::ID) {
+        let joined = self.next_joined;
+        self.next_joined += 1;
+        Arc::make_mut(&mut self.joined).insert(staff_id.clone(), joined);
+    }
+
+    fn sort_ids(&self, ids: &mut [V::ID]) {
+        match self.order {
+            StaffOrder::Unordered => {}
+            StaffOrder::Insertion => ids.sort_by_key(|id| *self.joined.get(id).unwrap()),
+            StaffOrder::Sorted => ids.sort_by(self.compare_ids.unwrap()),
+        }
+    }
+
+    /// Returns the direct subordinates in the order they are paid or credited, which is the
+    /// order of [`Company::subordinates_in_order`] falling back to the joining order, so payouts
+    /// do not depend on hash map order.
+    fn payout_order(&self, staff_id: &V::ID) -> Vec<V::ID> {
+        let mut ids: Vec<V::ID> = self
+            .subordinate_set(staff_id)
+            .map(|ids| ids.iter().cloned().collect())
+            .unwrap_or_default();
+        match self.order {
+            StaffOrder::Unordered => ids.sort_by_key(|id| *self.joined.get(id).unwrap()),
+            _ => self.sort_ids(&mut ids),
+        }
+        ids
+    }
+
+    fn resources_mut(&mut self) -> &mut B::Map<R> {
+        Arc::make_mut(&mut self.resources)
+    }
+
+    fn supervisors_mut(&mut self) -> &mut B::Map<V::ID> {
+        Arc::make_mut(&mut self.supervisors)
+    }
+
+    fn subordinates_mut(&mut self) -> &mut B::Map<Arc<B::Set>> {
+        Arc::make_mut(&mut self.subordinates)
+    }
+
+    fn credit(&mut self, staff_id: &V::ID, amount: R) {
+        let resources = self.resources_mut();
+        match resources.get_mut(staff_id) {
+            Some(resource) => *resource = *resource + amount,
+            None => {
+                resources.insert(staff_id.clone(), amount);
+            }
+        }
+    }
+
+    /// Takes resources from a staff member's balance, reserved resources can not be taken.
+    fn debit(&mut self, staff_id: &V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        self.check_available(staff_id, amount)?;
+        let resource = self.resources_mut().get_mut(staff_id).unwrap();
+        *resource = *resource - amount;
+        Ok(())
+    }
+}
+
+impl<V, R, B> Company<V, R, B>
+where
+    V: StaffEntity,
+    R: Resource,
+    B: Backend<V::ID, Set = HashSet<V::ID>>,
+{
+    /// Returns the staff's subordiantes
+    ///
+    /// Other backends provide the subordinates with [`Company::subordinate_set`].
+    pub fn subordinates(&self, staff_id: &V::ID) -> Option<&HashSet<V::ID>> {
+        self.subordinate_set(staff_id)
+    }
+}
+
+impl<V: StaffEntity + Clone, R: Resource, B: Backend<V::ID>> Clone for Company<V, R, B> {
+    /// Clones the company sharing its staff and balances until either copy changes them.
+    fn clone(&self) -> Self {
+        Self {
+            ceo_id: self.ceo_id.clone(),
+            staff: self.staff.share(V::clone),
+            resources: Arc::clone(&self.resources),
+            supervisors: Arc::clone(&self.supervisors),
+            subordinates: Arc::clone(&self.subordinates),
+            rounding: self.rounding,
+            dust: self.dust,
+            minted: self.minted,
+            burned: self.burned,
+            reservations: self.reservations.clone(),
+            reserved: self.reserved.clone(),
+            next_reservation_id: self.next_reservation_id,
+            limits: self.limits.clone(),
+            spent: self.spent.clone(),
+            period: self.period,
+            approval_policy: self.approval_policy,
+            requests: self.requests.clone(),
+            next_request_id: self.next_request_id,
+            structure: self.structure,
+            order: self.order,
+            compare_ids: self.compare_ids,
+            joined: Arc::clone(&self.joined),
+            next_joined: self.next_joined,
+        }
+    }
+}
+
+impl<V: StaffEntity, R: Resource, B: Backend<V::ID>> Company<V, R, B> {
+    /// Takes the company apart into collections owning all of its data.
+    pub(crate) fn into_parts(mut self) -> CompanyParts<V, R> {
+        let ids: Vec<_> = self.staff.keys().cloned().collect();
+        let staff = ids.iter().filter_map(|id| self.staff.remove(id)).collect();
+
+        CompanyParts {
+            ceo_id: self.ceo_id,
+            staff,
+            resources: pairs(self.resources.iter()),
+            supervisors: pairs(self.supervisors.iter()),
+            subordinates: self
+                .subordinates
+                .iter()
+                .map(|(id, subordinates)| (id.clone(), subordinates.iter().cloned().collect()))
+                .collect(),
+            rounding: self.rounding,
+            dust: self.dust,
+            minted: self.minted,
+            burned: self.burned,
+            reservations: pairs(&self.reservations),
+            reserved: pairs(&self.reserved),
+            next_reservation_id: self.next_reservation_id,
+            limits: pairs(&self.limits),
+            spent: pairs(&self.spent),
+            period: self.period,
+            approval_policy: self.approval_policy,
+            requests: pairs(&self.requests),
+            next_request_id: self.next_request_id,
+            structure: self.structure,
+            order: self.order,
+            compare_ids: self.compare_ids,
+            joined: pairs(self.joined.iter()),
+            next_joined: self.next_joined,
+        }
+    }
+
+    /// Puts together a company taken apart by [`Company::into_parts`].
+    pub(crate) fn from_parts(parts: CompanyParts<V, R>) -> Self {
+        let mut staff = B::Map::default();
+        for member in parts.staff {
+            staff.insert(member.get_id(), Arc::new(member));
+        }
+
+        Self {
+            ceo_id: parts.ceo_id,
+            staff: Members::new(staff),
+            resources: Arc::new(map_from(parts.resources)),
+            supervisors: Arc::new(map_from(parts.supervisors)),
+            subordinates: Arc::new(map_from(
+                parts
+                    .subordinates
+                    .into_iter()
+                    .map(|(id, subordinates)| {
+                        let mut set = B::Set::default();
+                        for subordinate in subordinates {
+                            set.insert(subordinate);
+                        }
+                        (id, Arc::new(set))
+                    })
+                    .collect(),
+            )),
+            rounding: parts.rounding,
+            dust: parts.dust,
+            minted: parts.minted,
+            burned: parts.burned,
+            reservations: parts.reservations.into_iter().collect(),
+            reserved: parts.reserved.into_iter().collect(),
+            next_reservation_id: parts.next_reservation_id,
+            limits: parts.limits.into_iter().collect(),
+            spent: parts.spent.into_iter().collect(),
+            period: parts.period,
+            approval_policy: parts.approval_policy,
+            requests: parts.requests.into_iter().collect(),
+            next_request_id: parts.next_request_id,
+            structure: parts.structure,
+            order: parts.order,
+            compare_ids: parts.compare_ids,
+            joined: Arc::new(map_from(parts.joined)),
+            next_joined: parts.next_joined,
+        }
+    }
+}
+
+/// A company taken apart by [`Company::into_parts`].
+///
+/// Nothing in it is shared with another company, so it can be sent to another thread when staff
+/// members, their IDs and resources are `Send`, while a [`Company`] also needs them to be `Sync`.
+pub(crate) struct CompanyParts<V: StaffEntity, R> {
+    ceo_id: V::ID,
+    staff: Vec<V>,
+    resources: Vec<(V::ID, R)>,
+    supervisors: Vec<(V::ID, V::ID)>,
+    subordinates: Vec<(V::ID, Vec<V::ID>)>,
+    rounding: Rounding,
+    dust: R,
+    minted: R,
+    burned: R,
+    reservations: Vec<(ReservationId, Reservation<V::ID, R>)>,
+    reserved: Vec<(V::ID, R)>,
+    next_reservation_id: u64,
+    limits: Vec<(V::ID, Limits<R>)>,
+    spent: Vec<(V::ID, R)>,
+    period: u64,
+    approval_policy: Option<ApprovalPolicy<R>>,
+    requests: Vec<(RequestId, TransferRequest<V::ID, R>)>,
+    next_request_id: u64,
+    structure: StructureRules,
+    order: StaffOrder,
+    compare_ids: Option<CompareIds<V::ID>>,
+    joined: Vec<(V::ID, u64)>,
+    next_joined: u64,
+}
+
+/// Staff members of a company, shared one by one between clones of the company.
+///
+/// A shared member is copied with `clone` when either company changes it. Members become shared
+/// only through [`Members::share`], which stores `clone` in both copies before sharing anything,
+/// so `clone` is always set when it is needed.
+struct Members<V: StaffEntity, B: Backend<V::ID>> {
+    map: Arc<B::Map<Arc<V>>>,
+    clone: OnceLock<fn(&V) -> V>,
+}
+
+impl<V: StaffEntity, B: Backend<V::ID>> Members<V, B> {
+    fn new(map: B::Map<Arc<V>>) -> Self {
+        Self {
+            map: Arc::new(map),
+            clone: OnceLock::new(),
+        }
+    }
+
+    /// Returns a copy sharing all members, `clone` copies a member changed by either copy.
+    fn share(&self, clone: fn(&V) -> V) -> Self {
+        let clone = *self.clone.get_or_init(|| clone);
+        Self {
+            map: Arc::clone(&self.map),
+            clone: OnceLock::from(clone),
+        }
+    }
+
+    fn get(&self, staff_id: &V::ID) -> Option<&V> {
+        self.map.get(staff_id).map(Arc::as_ref)
+    }
+
+    fn get_mut(&mut self, staff_id: &V::ID) -> Option<&mut V> {
+        let member = Arc::make_mut(&mut self.map).get_mut(staff_id)?;
+        if Arc::get_mut(member).is_none() {
+            *member = Arc::new(copy(&self.clone, member));
+        }
+        Arc::get_mut(member)
+    }
+
+    fn insert(&mut self, staff_id: V::ID, staff: V) {
+        Arc::make_mut(&mut self.map).insert(staff_id, Arc::new(staff));
+    }
+
+    fn remove(&mut self, staff_id: &V::ID) -> Option<V> {
+        let member = Arc::make_mut(&mut self.map).remove(staff_id)?;
+        Some(Arc::try_unwrap(member).unwrap_or_else(|member| copy(&self.clone, &member)))
+    }
+
+    fn contains_key(&self, staff_id: &V::ID) -> bool {
+        self.map.contains_key(staff_id)
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &V::ID> {
+        self.map.keys()
+    }
+}
+
+/// Copies a shared member.
+fn copy<V>(clone: &OnceLock<fn(&V) -> V>, member: &V) -> V {
+    let clone = clone
+        .get()
+        .expect("members are shared only by `Members::share` which sets `clone`");
+    clone(member)
+}
+
+/// Copies the entries of a map.
+fn pairs<'a, K: Clone + 'a, T: Clone + 'a>(
+    map: impl IntoIterator<Item = (&'a K, &'a T)>,
+) -> Vec<(K, T)> {
+    map.into_iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// Returns a map holding the given values.
+fn map_from<ID, T: Clone, M: StaffMap<ID, T>>(values: Vec<(ID, T)>) -> M {
+    let mut map = M::default();
+    for (id, value) in values {
+        map.insert(id, value);
+    }
+    map
+}
+
+/// Returns a map holding a single value.
+fn map_of<ID, T: Clone, M: StaffMap<ID, T>>(id: ID, value: T) -> M {
+    let mut map = M::default();
+    map.insert(id, value);
+    map
+}
+
+/// Checks that a percentage is between `0` and `100`.
+fn check_percentage<ID, R: Resource>(percentage: R) -> Result<(), Error<ID, R>> {
+    if percentage < R::zero() || percentage > R::from_u8(100).unwrap() {
+        return Err(Error::InvalidPercentage { percentage });
+    }
+
+    Ok(())
 }
@@ -0,0 +1,302 @@
+//! Module implements import and export of a [`Company`] hierarchy as CSV with the columns
+//! `id,supervisor_id,balance`.
+//!
+//! The CEO is the single row with an empty `supervisor_id`. A header row starting with `id` is
+//! optional on import and always written on export. Fields are separated by commas and trimmed,
+//! quoting is not supported, so IDs must not contain commas.
+//!
+//! Only staff IDs, the hierarchy and balances are stored. An imported company counts all
+//! balances as minted.
+
+use crate::company::Company;
+use crate::resource::Resource;
+use crate::storage::{Backend, StaffSet};
+use crate::traits::StaffEntity;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// Error of [`Company::from_csv`].
+///
+/// Line numbers start from `1` and include the header row.
+#[derive(Debug)]
+pub enum CsvError<ID> {
+    /// Reading the input failed.
+    Io(io::Error),
+
+    /// A row can not be parsed.
+    ///
+    /// # Parameters
+    /// * `line`: The number of the invalid row.
+    /// * `message`: What is wrong with the row.
+    Malformed { line: usize, message: &'static str },
+
+    /// The same staff ID is used by several rows.
+    ///
+    /// # Parameters
+    /// * `line`: The number of the repeated row.
+    /// * `first_line`: The number of the row where the ID was first used.
+    /// * `staff_id`: The repeated ID.
+    DuplicateId {
+        line: usize,
+        first_line: usize,
+        staff_id: ID,
+    },
+
+    /// No row has an empty `supervisor_id`.
+    MissingCeo,
+
+    /// Several rows have an empty `supervisor_id`.
+    ///
+    /// # Parameters
+    /// * `lines`: The numbers of all such rows.
+    MultipleCeos { lines: Vec<usize> },
+
+    /// The supervisor of a staff member is not in the file.
+    ///
+    /// # Parameters
+    /// * `line`: The number of the row.
+    /// * `staff_id`: The staff member.
+    /// * `supervisor_id`: The missing supervisor.
+    Orphan {
+        line: usize,
+        staff_id: ID,
+        supervisor_id: ID,
+    },
+
+    /// Staff members supervise each other in a cycle, so they can not be reached from the CEO.
+    ///
+    /// # Parameters
+    /// * `lines`: The numbers of the rows forming the cycle, each row is supervised by the next
+    ///   one and the last by the first.
+    Cycle { lines: Vec<usize> },
+
+    /// The staff constructor returned a staff member with a different ID than requested.
+    ///
+    /// # Parameters
+    /// * `line`: The number of the row.
+    /// * `staff_id`: The requested ID.
+    IdMismatch { line: usize, staff_id: ID },
+}
+
+impl<ID> From<io::Error> for CsvError<ID> {
+    fn from(error: io::Error) -> Self {
+        CsvError::Io(error)
+    }
+}
+
+/// Parsed CSV row.
+struct Row<ID, R> {
+    line: usize,
+    staff_id: ID,
+    supervisor_id: Option<ID>,
+    balance: R,
+}
+
+impl<V, R, B> Company<V, R, B>
+where
+    V: StaffEntity,
+    V::ID: FromStr,
+    R: Resource,
+    B: Backend<V::ID>,
+{
+    /// Builds a company from CSV rows of `id,supervisor_id,balance`.
+    ///
+    /// # Arguments
+    /// - `reader`: The CSV input.
+    /// - `make_staff`: Constructs the staff member for an ID.
+    ///
+    /// # Errors
+    /// - [`CsvError::Io`] if reading fails.
+    /// - [`CsvError::Malformed`] if a row does not have three fields, or an ID or balance can not
+    ///   be parsed, or a balance is negative.
+    /// - [`CsvError::DuplicateId`], [`CsvError::MissingCeo`], [`CsvError::MultipleCeos`],
+    ///   [`CsvError::Orphan`] or [`CsvError::Cycle`] if the rows do not form a hierarchy.
+    /// - [`CsvError::IdMismatch`] if `make_staff` returns a staff member with another ID.
+    pub fn from_csv(
+        reader: impl BufRead,
+        mut make_staff: impl FnMut(&V::ID) -> V,
+    ) -> Result<Self, CsvError<V::ID>> {
+        let rows = parse_rows::<V::ID, R>(reader)?;
+
+        let mut index = HashMap::new();
+        for (i, row) in rows.iter().enumerate() {
+            if let Some(first) = index.insert(row.staff_id.clone(), i) {
+                return Err(CsvError::DuplicateId {
+                    line: row.line,
+                    first_line: rows[first].line,
+                    staff_id: row.staff_id.clone(),
+                });
+            }
+        }
+
+        let ceos: Vec<&Row<V::ID, R>> = rows
+            .iter()
+            .filter(|row| row.supervisor_id.is_none())
+            .collect();
+        let ceo = match ceos.as_slice() {
+            [] => return Err(CsvError::MissingCeo),
+            [ceo] => *ceo,
+            _ => {
+                return Err(CsvError::MultipleCeos {
+                    lines: ceos.iter().map(|row| row.line).collect(),
+                })
+            }
+        };
+
+        let mut subordinates: HashMap<&V::ID, Vec<usize>> = HashMap::new();
+        for (i, row) in rows.iter().enumerate() {
+            let Some(supervisor_id) = &row.supervisor_id else {
+                continue;
+            };
+            if !index.contains_key(supervisor_id) {
+                return Err(CsvError::Orphan {
+                    line: row.line,
+                    staff_id: row.staff_id.clone(),
+                    supervisor_id: supervisor_id.clone(),
+                });
+            }
+            subordinates.entry(supervisor_id).or_default().push(i);
+        }
+
+        let mut make = |row: &Row<V::ID, R>| {
+            let staff = make_staff(&row.staff_id);
+            if staff.get_id() != row.staff_id {
+                return Err(CsvError::IdMismatch {
+                    line: row.line,
+                    staff_id: row.staff_id.clone(),
+                });
+            }
+            Ok(staff)
+        };
+
+        let mut company = Company::new(make(ceo)?);
+        company.mint(ceo.balance);
+
+        let mut reached = HashSet::from([&ceo.staff_id]);
+        let mut queue = VecDeque::from([&ceo.staff_id]);
+        while let Some(supervisor_id) = queue.pop_front() {
+            for &i in subordinates.get(supervisor_id).into_iter().flatten() {
+                let row = &rows[i];
+                company.insert_staff(make(row)?, supervisor_id, row.balance);
+                reached.insert(&row.staff_id);
+                queue.push_back(&row.staff_id);
+            }
+        }
+
+        if let Some(start) = rows.iter().find(|row| !reached.contains(&row.staff_id)) {
+            return Err(CsvError::Cycle {
+                lines: find_cycle(&rows, &index, start),
+            });
+        }
+
+        Ok(company)
+    }
+}
+
+impl<V, R, B> Company<V, R, B>
+where
+    V: StaffEntity,
+    V::ID: Ord + Display,
+    R: Resource + Display,
+    B: Backend<V::ID>,
+{
+    /// Writes the hierarchy as CSV rows of `id,supervisor_id,balance` preceded by a header.
+    ///
+    /// Rows are written in breadth-first order from the CEO, subordinates sorted by ID, so every
+    /// supervisor precedes its subordinates.
+    ///
+    /// # Errors
+    /// If writing fails.
+    pub fn to_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "id,supervisor_id,balance")?;
+
+        let Some(ceo) = self.ceo() else {
+            return Ok(());
+        };
+        let mut queue = VecDeque::from([ceo.get_id()]);
+        while let Some(staff_id) = queue.pop_front() {
+            let supervisor_id = self
+                .supervisor(&staff_id)
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            let balance = self.resource(&staff_id).unwrap();
+            writeln!(writer, "{staff_id},{supervisor_id},{balance}")?;
+
+            let mut subordinates: Vec<V::ID> = self
+                .subordinate_set(&staff_id)
+                .into_iter()
+                .flat_map(StaffSet::iter)
+                .cloned()
+                .collect();
+            subordinates.sort();
+            queue.extend(subordinates);
+        }
+        Ok(())
+    }
+}
+
+fn parse_rows<ID: FromStr, R: Resource>(
+    reader: impl BufRead,
+) -> Result<Vec<Row<ID, R>>, CsvError<ID>> {
+    let mut rows = Vec::new();
+    for (i, text) in reader.lines().enumerate() {
+        let text = text?;
+        let line = i + 1;
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = text.split(',').map(str::trim).collect();
+        let [staff_id, supervisor_id, balance] = fields.as_slice() else {
+            return Err(CsvError::Malformed {
+                line,
+                message: "expected 3 fields",
+            });
+        };
+        if line == 1 && *staff_id == "id" {
+            continue;
+        }
+
+        let malformed = |message| CsvError::Malformed { line, message };
+        let staff_id = staff_id.parse().map_err(|_| malformed("invalid id"))?;
+        let supervisor_id = match *supervisor_id {
+            "" => None,
+            id => Some(id.parse().map_err(|_| malformed("invalid supervisor id"))?),
+        };
+        let balance = R::from_str_radix(balance, 10).map_err(|_| malformed("invalid balance"))?;
+        if balance < R::zero() {
+            return Err(malformed("negative balance"));
+        }
+
+        rows.push(Row {
+            line,
+            staff_id,
+            supervisor_id,
+            balance,
+        });
+    }
+    Ok(rows)
+}
+
+/// Follows supervisors from `start` until a row repeats and returns the lines of the cycle.
+fn find_cycle<ID, R>(
+    rows: &[Row<ID, R>],
+    index: &HashMap<ID, usize>,
+    start: &Row<ID, R>,
+) -> Vec<usize>
+where
+    ID: Eq + std::hash::Hash,
+{
+    let mut path: Vec<usize> = Vec::new();
+    let mut row = start;
+    loop {
+        if let Some(position) = path.iter().position(|&line| line == row.line) {
+            return path.split_off(position);
+        }
+        path.push(row.line);
+        let supervisor_id = row.supervisor_id.as_ref().unwrap();
+        row = &rows[index[supervisor_id]];
+    }
+}
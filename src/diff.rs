@@ -0,0 +1,148 @@
+//! Module defines the [`CompanyDiff`] between two states of a company returned by
+//! [`Company::diff`].
+
+use crate::company::Company;
+use crate::resource::Resource;
+use crate::storage::Backend;
+use crate::traits::StaffEntity;
+use core::fmt::{self, Debug, Display};
+
+/// Supervisor change of a staff member present in both states.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupervisorChange<ID> {
+    pub staff_id: ID,
+    pub from: ID,
+    pub to: ID,
+}
+
+/// Balance change of a staff member.
+///
+/// The balance of a staff member missing in one of the states is zero there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceChange<ID, R> {
+    pub staff_id: ID,
+    pub before: R,
+    pub after: R,
+}
+
+/// Structured difference between two states of a company.
+///
+/// All lists are ordered by staff ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompanyDiff<ID, R> {
+    /// Staff members present only in the later state.
+    pub added: Vec<ID>,
+    /// Staff members present only in the earlier state.
+    pub removed: Vec<ID>,
+    /// Supervisor changes of staff members present in both states.
+    pub supervisor_changes: Vec<SupervisorChange<ID>>,
+    /// Balance changes, staff members whose balance is the same are omitted.
+    pub balance_changes: Vec<BalanceChange<ID, R>>,
+}
+
+impl<ID, R> CompanyDiff<ID, R> {
+    /// Returns whether the states have the same staff, hierarchy and balances.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.supervisor_changes.is_empty()
+            && self.balance_changes.is_empty()
+    }
+}
+
+impl<ID: Debug, R: Debug> Display for CompanyDiff<ID, R> {
+    /// Renders the diff as a report with one change per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no changes");
+        }
+
+        for staff_id in self.added.iter() {
+            writeln!(f, "+ {staff_id:?}")?;
+        }
+        for staff_id in self.removed.iter() {
+            writeln!(f, "- {staff_id:?}")?;
+        }
+        for change in self.supervisor_changes.iter() {
+            writeln!(
+                f,
+                "~ {:?}: supervisor {:?} -> {:?}",
+                change.staff_id, change.from, change.to
+            )?;
+        }
+        for change in self.balance_changes.iter() {
+            writeln!(
+                f,
+                "$ {:?}: {:?} -> {:?}",
+                change.staff_id, change.before, change.after
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<V, R, B> Company<V, R, B>
+where
+    V: StaffEntity,
+    V::ID: Ord,
+    R: Resource,
+    B: Backend<V::ID>,
+{
+    /// Returns the changes leading from this company to `other`.
+    ///
+    /// To compare with a [`crate::snapshot::Snapshot`] use
+    /// [`crate::snapshot::Snapshot::company`].
+    pub fn diff(&self, other: &Self) -> CompanyDiff<V::ID, R> {
+        let mut before = self.get_all_staff();
+        before.sort();
+        let mut after = other.get_all_staff();
+        after.sort();
+
+        let added = after
+            .iter()
+            .filter(|id| self.get(id).is_none())
+            .cloned()
+            .collect();
+        let removed = before
+            .iter()
+            .filter(|id| other.get(id).is_none())
+            .cloned()
+            .collect();
+
+        let supervisor_changes = before
+            .iter()
+            .filter_map(|id| match (self.supervisor(id), other.supervisor(id)) {
+                (Some(from), Some(to)) if from != to => Some(SupervisorChange {
+                    staff_id: id.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        let mut ids = before;
+        ids.extend(after);
+        ids.sort();
+        ids.dedup();
+        let balance_changes = ids
+            .into_iter()
+            .filter_map(|id| {
+                let before = self.resource(&id).copied().unwrap_or_else(R::zero);
+                let after = other.resource(&id).copied().unwrap_or_else(R::zero);
+                (before != after).then_some(BalanceChange {
+                    staff_id: id,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+
+        CompanyDiff {
+            added,
+            removed,
+            supervisor_changes,
+            balance_changes,
+        }
+    }
+}
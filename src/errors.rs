@@ -1,5 +1,10 @@
 //! Defines [`crate::errors::Error`] enum.
 
+use crate::approval::RequestId;
+use crate::limits::LimitKind;
+use crate::reservation::ReservationId;
+use crate::structure::StructureRule;
+
 /// Enum representing errors related to staff management in the company.
 #[derive(Debug)]
 pub enum Error<ID, R> {
@@ -9,10 +14,13 @@ pub enum Error<ID, R> {
     /// * `staff_id`: The unique identifier of the staff entity.
     /// * `required`: required resoucre amount to apply action.
     /// * `available `: required resoucre amount exists in staff member's balance.
+    /// * `reserved`: resources on the staff member's balance held by reservations, they are not
+    ///   included in `available`.
     InsufficientResourcesError {
         staff_id: ID,
         required: R,
         available: R,
+        reserved: R,
     },
 
     /// Error indicating that the staff entity with the given id not found.
@@ -45,4 +53,83 @@ pub enum Error<ID, R> {
 
     /// Error indicating that a staff entity can not be a supervisor for another
     HierarchyConflict { staff_id: ID, supervisor_id: ID },
+
+    /// Error indicating that the balances of staff members do not add up to the company's
+    /// circulating supply.
+    ///
+    /// # Parameters
+    /// * `supply`: minted resources which have not been burned.
+    /// * `balances`: sum of all staff balances and the dust account.
+    SupplyMismatch { supply: R, balances: R },
+
+    /// Error indicating that the reservation with the given id not found.
+    ///
+    /// It is commonly caused when a reservation is used after being released or committed.
+    ///
+    /// # Parameters
+    /// * `reservation_id`: The unique identifier of the reservation.
+    ReservationNotFound { reservation_id: ReservationId },
+
+    /// Error indicating that an operation would exceed one of the staff member's limits.
+    ///
+    /// # Parameters
+    /// * `staff_id`: The unique identifier of the limited staff entity.
+    /// * `kind`: Which of the limits is exceeded.
+    /// * `limit`: The value of the limit.
+    /// * `attempted`: The amount the operation would bring the limited value to.
+    LimitExceeded {
+        staff_id: ID,
+        kind: LimitKind,
+        limit: R,
+        attempted: R,
+    },
+
+    /// Error indicating that a transfer exceeds the approval threshold and must be requested
+    /// with [`crate::Company::request_transfer`].
+    ///
+    /// # Parameters
+    /// * `staff_id`: The unique identifier of the sender.
+    /// * `threshold`: The approval threshold.
+    /// * `amount`: The amount of the transfer.
+    ApprovalRequired {
+        staff_id: ID,
+        threshold: R,
+        amount: R,
+    },
+
+    /// Error indicating that the transfer request with the given id not found.
+    ///
+    /// # Parameters
+    /// * `request_id`: The unique identifier of the request.
+    RequestNotFound { request_id: RequestId },
+
+    /// Error indicating that a transfer request would have nobody to approve it.
+    ///
+    /// It is commonly caused when the CEO requests a transfer or when no approval policy is set,
+    /// such transfers are made with [`crate::Company::transfer`] instead.
+    ///
+    /// # Parameters
+    /// * `staff_id`: The unique identifier of the sender.
+    ApprovalNotRequired { staff_id: ID },
+
+    /// Error indicating that an operation would violate one of the company's structural rules.
+    ///
+    /// # Parameters
+    /// * `staff_id`: The unique identifier of the supervisor whose reports would violate the
+    ///   rule or of the staff member which would be placed too deep.
+    /// * `rule`: Which of the rules is violated.
+    /// * `limit`: The value of the rule.
+    /// * `attempted`: The number of reports or the depth the operation would lead to.
+    StructureViolation {
+        staff_id: ID,
+        rule: StructureRule,
+        limit: usize,
+        attempted: usize,
+    },
+
+    /// Error indicating that a percentage given to an operation is not between `0` and `100`.
+    ///
+    /// # Parameters
+    /// * `percentage`: The invalid percentage.
+    InvalidPercentage { percentage: R },
 }
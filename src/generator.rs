@@ -0,0 +1,236 @@
+//! Module defines the [`Generator`] of random companies and random [`Operation`] sequences.
+//!
+//! The generator is driven by a seeded [`ChaCha8Rng`], so the same seed, configuration and
+//! staff factory always produce the same company and the same operations.
+
+use crate::company::Company;
+use crate::operation::Operation;
+use crate::resource::{Resource, Rounding};
+use crate::traits::StaffEntity;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Number of already hired IDs in a row after which [`Generator::company`] gives up.
+pub const MAX_DUPLICATE_IDS: usize = 1000;
+
+/// How new staff members are attached to the hierarchy by [`Generator::company`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Branching {
+    /// Every eligible supervisor is equally likely.
+    #[default]
+    Uniform,
+    /// Supervisors with more subordinates are more likely, producing a few wide teams.
+    Preferential,
+    /// Supervisors with the fewest subordinates are chosen, producing a balanced tree.
+    Balanced,
+}
+
+/// Parameters of a company built by [`Generator::company`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompanyConfig<R> {
+    /// Number of staff members including the CEO.
+    pub size: usize,
+    /// Maximum number of levels below the CEO.
+    pub max_depth: usize,
+    /// Maximum number of direct subordinates of a supervisor, `None` means unlimited.
+    pub max_reports: Option<usize>,
+    /// How new staff members are attached to the hierarchy.
+    pub branching: Branching,
+    /// Amount minted before hiring starts.
+    pub initial_mint: R,
+}
+
+/// Relative weights of operation kinds picked by [`Generator::operation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperationWeights {
+    pub hire: u32,
+    pub fire: u32,
+    pub transfer: u32,
+    pub withdraw: u32,
+    pub mint: u32,
+    pub change_supervisor: u32,
+}
+
+impl Default for OperationWeights {
+    fn default() -> Self {
+        Self {
+            hire: 4,
+            fire: 2,
+            transfer: 6,
+            withdraw: 1,
+            mint: 1,
+            change_supervisor: 2,
+        }
+    }
+}
+
+/// Generator of random companies and operations.
+pub struct Generator {
+    rng: ChaCha8Rng,
+}
+
+impl Generator {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the generator's random number generator, e.g. for building staff members.
+    pub fn rng(&mut self) -> &mut ChaCha8Rng {
+        &mut self.rng
+    }
+
+    /// Builds a random company.
+    ///
+    /// Staff members are created by `make_staff` from the generator's RNG, so their IDs should
+    /// be derived from it to keep the result reproducible. Hiring stops early if no supervisor
+    /// satisfies `max_depth` and `max_reports` any more.
+    ///
+    /// # Panics
+    /// If `make_staff` returns an already hired ID [`MAX_DUPLICATE_IDS`] times in a row.
+    pub fn company<V, R, F>(
+        &mut self,
+        config: &CompanyConfig<R>,
+        mut make_staff: F,
+    ) -> Company<V, R>
+    where
+        V: StaffEntity,
+        R: Resource,
+        F: FnMut(&mut ChaCha8Rng) -> V,
+    {
+        let ceo = make_staff(&mut self.rng);
+        let mut ids = vec![ceo.get_id()];
+        let mut depths = vec![0];
+        let mut reports = vec![0];
+
+        let mut company = Company::new(ceo);
+        company.mint(config.initial_mint);
+
+        let mut duplicates = 0;
+        while ids.len() < config.size {
+            let eligible: Vec<usize> = (0..ids.len())
+                .filter(|&i| depths[i] < config.max_depth)
+                .filter(|&i| config.max_reports.is_none_or(|max| reports[i] < max))
+                .collect();
+            if eligible.is_empty() {
+                break;
+            }
+
+            let supervisor = match config.branching {
+                Branching::Uniform => eligible[self.rng.gen_range(0..eligible.len())],
+                Branching::Preferential => {
+                    let total: usize = eligible.iter().map(|&i| reports[i] + 1).sum();
+                    let mut point = self.rng.gen_range(0..total);
+                    *eligible
+                        .iter()
+                        .find(|&&i| {
+                            let weight = reports[i] + 1;
+                            if point < weight {
+                                return true;
+                            }
+                            point -= weight;
+                            false
+                        })
+                        .unwrap()
+                }
+                Branching::Balanced => *eligible.iter().min_by_key(|&&i| reports[i]).unwrap(),
+            };
+
+            let staff = make_staff(&mut self.rng);
+            match company.hire(staff, &ids[supervisor]) {
+                Ok(id) => ids.push(id),
+                // the factory returned an already hired ID
+                Err(_) => {
+                    duplicates += 1;
+                    assert!(
+                        duplicates < MAX_DUPLICATE_IDS,
+                        "staff factory returned {MAX_DUPLICATE_IDS} already hired IDs in a row"
+                    );
+                    continue;
+                }
+            }
+            duplicates = 0;
+            depths.push(depths[supervisor] + 1);
+            reports.push(0);
+            reports[supervisor] += 1;
+        }
+
+        company
+    }
+
+    /// Generates a random operation for the current state of `company`.
+    ///
+    /// Operations refer to existing staff members and transfer or withdraw at most the available
+    /// resources, but they may still fail, e.g. a supervisor change creating a cycle. Mints are
+    /// up to `max_mint`. Staff members are ordered by ID to keep the choice reproducible.
+    pub fn operation<V, R, F>(
+        &mut self,
+        company: &Company<V, R>,
+        weights: &OperationWeights,
+        max_mint: R,
+        mut make_staff: F,
+    ) -> Operation<V, R>
+    where
+        V: StaffEntity,
+        V::ID: Ord,
+        R: Resource,
+        F: FnMut(&mut ChaCha8Rng) -> V,
+    {
+        let mut ids = company.get_all_staff();
+        ids.sort();
+        let ceo_id = company.ceo().unwrap().get_id();
+        let others: Vec<&V::ID> = ids.iter().filter(|id| **id != ceo_id).collect();
+
+        let kinds = [
+            weights.hire,
+            weights.fire,
+            weights.transfer,
+            weights.withdraw,
+            weights.mint,
+            weights.change_supervisor,
+        ];
+        let mut point = self.rng.gen_range(0..kinds.iter().sum::<u32>().max(1));
+        let mut kind = 0;
+        while kind < kinds.len() - 1 && point >= kinds[kind] {
+            point -= kinds[kind];
+            kind += 1;
+        }
+        if others.is_empty() && (kind == 1 || kind == 5) {
+            kind = 0;
+        }
+
+        let staff_id = ids[self.rng.gen_range(0..ids.len())].clone();
+        let part = |rng: &mut ChaCha8Rng, amount: R| {
+            let percentage = R::from_u8(rng.gen_range(0..=100)).unwrap();
+            (amount * percentage).div_rounded(R::from_u8(100).unwrap(), Rounding::Floor)
+        };
+
+        match kind {
+            0 => Operation::Hire {
+                staff: make_staff(&mut self.rng),
+                supervisor_id: staff_id,
+            },
+            1 => Operation::Fire {
+                staff_id: others[self.rng.gen_range(0..others.len())].clone(),
+            },
+            2 => Operation::Transfer {
+                amount: part(&mut self.rng, company.available(&staff_id).unwrap()),
+                to: ids[self.rng.gen_range(0..ids.len())].clone(),
+                from: staff_id,
+            },
+            3 => Operation::Withdraw {
+                amount: part(&mut self.rng, company.available(&staff_id).unwrap()),
+                staff_id,
+            },
+            4 => Operation::Mint {
+                amount: part(&mut self.rng, max_mint),
+            },
+            _ => Operation::ChangeSupervisor {
+                staff_id: others[self.rng.gen_range(0..others.len())].clone(),
+                supervisor_id: staff_id,
+            },
+        }
+    }
+}
@@ -2,17 +2,50 @@
 //! opportunities to `hire` and `layoff` staff members and flexible resouce shring between them.
 //!
 //! Key module elements:
-//! - Traits: [`crate::traits::StaffEntity`]
-//! - Struct: [`crate::company::Company`]
+//! - Traits: [`crate::traits::StaffEntity`], [`crate::resource::Resource`]
+//! - Struct: [`crate::company::Company`], [`crate::resource::Fixed`]
 //!
 //!
 //! # Library design overview:
 //! - Only `Company` owns all the data stored in it
 //! - All staff entites act as `view` objects used for interaction with the company object, but do not hold data themselves.
+//! - `Company` is `Send + Sync` when its staff, IDs and resources are, share it between threads
+//!   with [`crate::shared::SharedCompany`].
+//! - Staff, balances and relations are kept in maps of a [`crate::storage::Backend`],
+//!   the persistent [`crate::storage::PersistentBackend`] unless another one is chosen.
+//!   [`crate::shared`], [`crate::actor`], [`crate::simulation`] and the persistence modules take
+//!   the backend as a type parameter as well.
 
+pub mod actor;
+pub mod approval;
+pub mod cascade;
+pub mod chart;
 pub mod company;
+pub mod csv;
+pub mod diff;
 pub mod errors;
+pub mod generator;
+pub mod limits;
+pub mod operation;
+pub mod order;
+pub mod payroll;
+pub mod plan;
+pub mod reservation;
+pub mod resource;
+pub mod shared;
+pub mod simulation;
+pub mod snapshot;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod storage;
+pub mod structure;
+pub mod sweep;
 pub mod traits;
+pub mod tree;
+#[cfg(feature = "persistence")]
+pub mod wal;
 
 pub use company::Company;
+pub use resource::{Fixed, Resource, Rounding};
 pub use traits::StaffEntity;
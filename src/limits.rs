@@ -0,0 +1,41 @@
+//! Module defines spending [`Limits`] of staff members and the [`LimitKind`] reported when one of
+//! them is exceeded.
+
+/// Spending limits of a staff member.
+///
+/// Every limit is optional, `None` means that the corresponding amount is not restricted.
+/// Limits are checked by [`crate::Company::withdraw`] and [`crate::Company::transfer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits<R> {
+    /// Maximum balance a staff member can reach by receiving transfers.
+    pub max_balance: Option<R>,
+    /// Maximum amount of a single transfer or withdrawal.
+    pub max_transfer: Option<R>,
+    /// Maximum amount transferred and withdrawn during one period
+    /// (see [`crate::Company::advance_period`]).
+    pub period_spending: Option<R>,
+    /// Whether subordinates without own limits are restricted by these limits too.
+    pub inherit: bool,
+}
+
+impl<R> Default for Limits<R> {
+    fn default() -> Self {
+        Self {
+            max_balance: None,
+            max_transfer: None,
+            period_spending: None,
+            inherit: false,
+        }
+    }
+}
+
+/// Kind of a limit reported by [`crate::errors::Error::LimitExceeded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    /// [`Limits::max_balance`] was exceeded.
+    MaxBalance,
+    /// [`Limits::max_transfer`] was exceeded.
+    MaxTransfer,
+    /// [`Limits::period_spending`] was exceeded.
+    PeriodSpending,
+}
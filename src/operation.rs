@@ -0,0 +1,110 @@
+//! Module defines the [`Operation`] enum describing a single mutation of a
+//! [`crate::Company`] which can be stored, generated or replayed.
+
+use crate::company::Company;
+use crate::errors::Error;
+use crate::resource::Resource;
+use crate::storage::Backend;
+use crate::traits::StaffEntity;
+
+/// A single mutating operation of a [`Company`].
+///
+/// Every variant mirrors the [`Company`] method with the same name.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "persistence",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "V: serde::Serialize, V::ID: serde::Serialize, R: serde::Serialize",
+        deserialize = "V: serde::Deserialize<'de>, V::ID: serde::Deserialize<'de>, R: serde::Deserialize<'de>"
+    ))
+)]
+pub enum Operation<V: StaffEntity, R> {
+    Hire {
+        staff: V,
+        supervisor_id: V::ID,
+    },
+    Fire {
+        staff_id: V::ID,
+    },
+    Transfer {
+        from: V::ID,
+        to: V::ID,
+        amount: R,
+    },
+    Withdraw {
+        staff_id: V::ID,
+        amount: R,
+    },
+    Mint {
+        amount: R,
+    },
+    ChangeSupervisor {
+        staff_id: V::ID,
+        supervisor_id: V::ID,
+    },
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, V, R> arbitrary::Arbitrary<'a> for Operation<V, R>
+where
+    V: StaffEntity + arbitrary::Arbitrary<'a>,
+    V::ID: arbitrary::Arbitrary<'a>,
+    R: arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=5)? {
+            0 => Operation::Hire {
+                staff: u.arbitrary()?,
+                supervisor_id: u.arbitrary()?,
+            },
+            1 => Operation::Fire {
+                staff_id: u.arbitrary()?,
+            },
+            2 => Operation::Transfer {
+                from: u.arbitrary()?,
+                to: u.arbitrary()?,
+                amount: u.arbitrary()?,
+            },
+            3 => Operation::Withdraw {
+                staff_id: u.arbitrary()?,
+                amount: u.arbitrary()?,
+            },
+            4 => Operation::Mint {
+                amount: u.arbitrary()?,
+            },
+            _ => Operation::ChangeSupervisor {
+                staff_id: u.arbitrary()?,
+                supervisor_id: u.arbitrary()?,
+            },
+        })
+    }
+}
+
+impl<V: StaffEntity, R: Resource, B: Backend<V::ID>> Company<V, R, B> {
+    /// Applies an [`Operation`] by calling the corresponding method.
+    ///
+    /// Values returned by the methods, like the fired staff member, are dropped.
+    ///
+    /// # Errors
+    /// - Errors of the called method.
+    pub fn apply(&mut self, operation: Operation<V, R>) -> Result<(), Error<V::ID, R>> {
+        match operation {
+            Operation::Hire {
+                staff,
+                supervisor_id,
+            } => self.hire(staff, &supervisor_id).map(|_| ()),
+            Operation::Fire { staff_id } => self.fire(&staff_id).map(|_| ()),
+            Operation::Transfer { from, to, amount } => self.transfer(&from, &to, amount),
+            Operation::Withdraw { staff_id, amount } => self.withdraw(&staff_id, amount),
+            Operation::Mint { amount } => {
+                self.mint(amount);
+                Ok(())
+            }
+            Operation::ChangeSupervisor {
+                staff_id,
+                supervisor_id,
+            } => self.change_supervisor(&staff_id, &supervisor_id),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Module defines the [`StaffOrder`] modes of listing staff members.
+
+use core::cmp::Ordering;
+
+/// Function comparing staff IDs.
+pub(crate) type CompareIds<ID> = fn(&ID, &ID) -> Ordering;
+
+/// Order in which [`crate::company::Company::get_all_staff`] and
+/// [`crate::company::Company::subordinates_in_order`] list staff members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StaffOrder {
+    /// Hash map order, which is the fastest but changes from run to run. This is the default.
+    #[default]
+    Unordered,
+
+    /// The order in which staff members joined the company, the CEO first.
+    Insertion,
+
+    /// Ascending order of staff IDs.
+    Sorted,
+}
@@ -0,0 +1,47 @@
+//! Module defines the [`PayrollRule`] used by [`crate::Company::run_payroll`] and the
+//! [`PayrollReport`] describing a payroll round.
+
+/// Rule deciding how much every supervisor pays to its direct subordinates during a payroll.
+pub enum PayrollRule<'a, V, R> {
+    /// Every subordinate receives the same fixed salary from its supervisor.
+    FixedSalary(R),
+
+    /// Every supervisor pays the given percentage of its available resources, split equally
+    /// between its subordinates.
+    Percentage(R),
+
+    /// Every supervisor pays `percentage` of its available resources, split between its
+    /// subordinates proportionally to the weights returned by `weight`.
+    Weighted {
+        percentage: R,
+        weight: &'a dyn Fn(&V) -> R,
+    },
+}
+
+/// Resources paid by a supervisor to a subordinate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Payout<ID, R> {
+    pub from: ID,
+    pub to: ID,
+    pub amount: R,
+}
+
+/// Payment which could not be made because the supervisor had insufficient resources.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortfall<ID, R> {
+    pub from: ID,
+    pub to: ID,
+    pub required: R,
+    pub available: R,
+}
+
+/// Summary of a payroll round.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayrollReport<ID, R> {
+    /// Payments made, in the order they were executed.
+    pub payouts: Vec<Payout<ID, R>>,
+    /// Payments which were skipped.
+    pub shortfalls: Vec<Shortfall<ID, R>>,
+    /// Resources added to the company's dust account by rounding.
+    pub dust: R,
+}
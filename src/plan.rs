@@ -0,0 +1,118 @@
+//! Module defines the [`Plan`] returned by [`Company::plan`], a dry run of a sequence of
+//! [`Operation`]s.
+
+use crate::company::Company;
+use crate::diff::CompanyDiff;
+use crate::errors::Error;
+use crate::operation::Operation;
+use crate::resource::Resource;
+use crate::storage::{Backend, PersistentBackend};
+use crate::traits::StaffEntity;
+
+/// Outcome of a single planned operation.
+#[derive(Debug)]
+pub struct PlanStep<V: StaffEntity, R> {
+    pub operation: Operation<V, R>,
+    /// The error the operation would fail with, if any.
+    pub result: Result<(), Error<V::ID, R>>,
+    /// Changes made by the operation, empty if it fails.
+    pub diff: CompanyDiff<V::ID, R>,
+}
+
+/// Dry run of a sequence of operations.
+pub struct Plan<V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    steps: Vec<PlanStep<V, R>>,
+    diff: CompanyDiff<V::ID, R>,
+    company: Company<V, R, B>,
+}
+
+impl<V, R, B> Plan<V, R, B>
+where
+    V: StaffEntity,
+    V::ID: Ord,
+    R: Resource,
+    B: Backend<V::ID>,
+{
+    /// Returns the outcome of every operation in order.
+    pub fn steps(&self) -> &[PlanStep<V, R>] {
+        &self.steps
+    }
+
+    /// Returns the changes made by all operations together.
+    pub fn diff(&self) -> &CompanyDiff<V::ID, R> {
+        &self.diff
+    }
+
+    /// Returns whether every operation would succeed.
+    pub fn is_ok(&self) -> bool {
+        self.steps.iter().all(|step| step.result.is_ok())
+    }
+
+    /// Returns the errors of failing operations together with their indices.
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &Error<V::ID, R>)> {
+        self.steps
+            .iter()
+            .enumerate()
+            .filter_map(|(index, step)| step.result.as_ref().err().map(|error| (index, error)))
+    }
+
+    /// Returns the balances of all staff members after the operations, ordered by staff ID.
+    pub fn balances(&self) -> Vec<(V::ID, R)> {
+        let mut ids = self.company.get_all_staff();
+        ids.sort();
+        ids.into_iter()
+            .map(|id| {
+                let balance = *self.company.resource(&id).unwrap();
+                (id, balance)
+            })
+            .collect()
+    }
+
+    /// Returns the company as it would be after the operations.
+    pub fn company(&self) -> &Company<V, R, B> {
+        &self.company
+    }
+
+    /// Consumes the plan returning the company as it would be after the operations.
+    pub fn into_company(self) -> Company<V, R, B> {
+        self.company
+    }
+}
+
+impl<V, R, B> Company<V, R, B>
+where
+    V: StaffEntity + Clone,
+    V::ID: Ord,
+    R: Resource,
+    B: Backend<V::ID>,
+{
+    /// Runs `operations` on a copy of the company leaving the company itself untouched.
+    ///
+    /// Operations are applied with [`Company::apply`], so they behave exactly as on the real
+    /// company. A failing operation changes nothing and the following operations still run.
+    ///
+    /// # Returns
+    /// - The [`Plan`] with the outcome of every operation.
+    pub fn plan(&self, operations: impl IntoIterator<Item = Operation<V, R>>) -> Plan<V, R, B> {
+        let mut company = self.clone();
+        let steps = operations
+            .into_iter()
+            .map(|operation| {
+                let before = company.clone();
+                let result = company.apply(operation.clone());
+                let diff = before.diff(&company);
+                PlanStep {
+                    operation,
+                    result,
+                    diff,
+                }
+            })
+            .collect();
+
+        Plan {
+            steps,
+            diff: self.diff(&company),
+            company,
+        }
+    }
+}
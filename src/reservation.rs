@@ -0,0 +1,28 @@
+//! Module defines [`Reservation`] of staff member's resources and its identifier
+//! [`ReservationId`].
+
+/// Unique identifier of a [`Reservation`] inside a company.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ReservationId(pub(crate) u64);
+
+/// Part of a staff member's balance earmarked for a pending payment.
+///
+/// Reserved resources stay on the member's balance but can not be withdrawn or transferred
+/// until the reservation is released or committed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reservation<ID, R> {
+    pub(crate) staff_id: ID,
+    pub(crate) amount: R,
+}
+
+impl<ID, R: Copy> Reservation<ID, R> {
+    /// Returns the ID of the staff member whose resources are reserved.
+    pub fn staff_id(&self) -> &ID {
+        &self.staff_id
+    }
+
+    /// Returns the reserved amount.
+    pub fn amount(&self) -> R {
+        self.amount
+    }
+}
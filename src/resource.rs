@@ -0,0 +1,498 @@
+//! Module defines the [`Resource`] trait for company funds, the [`Rounding`] modes used when
+//! resources are split between staff members and the [`Fixed`] point resource type.
+
+use core::fmt::{self, Debug, Display};
+use core::ops::{Add, Div, Mul, Rem, Sub};
+use core::str::FromStr;
+use num_traits::{FromPrimitive, Num, One, PrimInt, ToPrimitive, Zero};
+
+/// Rounding mode applied when resources are divided between staff members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    /// Rounds toward negative infinity. This is the default and matches plain integer division
+    /// for non-negative balances.
+    #[default]
+    Floor,
+
+    /// Rounds toward positive infinity.
+    Ceil,
+
+    /// Rounds to the nearest value, resolving ties to the even one (banker's rounding).
+    HalfEven,
+}
+
+/// [`Resource`] is implemented by every numeric type which can be used as company funds.
+///
+/// It is implemented for all primitive integers and floats and for [`Fixed`].
+pub trait Resource: Num + Copy + PartialOrd + FromPrimitive + Debug {
+    /// Divides `self` by `rhs` rounding the result to the smallest representable unit of the
+    /// type according to `rounding`.
+    ///
+    /// Floats have no smallest unit, so for them the result is the plain quotient.
+    fn div_rounded(self, rhs: Self, rounding: Rounding) -> Self;
+
+    /// Computes `self * mul / div` rounding the result according to `rounding`.
+    ///
+    /// Integers and [`Fixed`] compute the product in double width, so the result is exact as
+    /// long as it fits the type. The default implementation multiplies first.
+    ///
+    /// # Panics
+    /// If `div` is zero or the result does not fit the type.
+    fn mul_div(self, mul: Self, div: Self, rounding: Rounding) -> Self {
+        (self * mul).div_rounded(div, rounding)
+    }
+
+    /// Computes `percentage` percent of `self` rounding the result according to `rounding`.
+    ///
+    /// It is computed with [`Resource::mul_div`], so it does not overflow for any percentage
+    /// between `0` and `100`.
+    fn percent_of(self, percentage: Self, rounding: Rounding) -> Self {
+        self.mul_div(percentage, Self::from_u8(100).unwrap(), rounding)
+    }
+}
+
+/// Divides two integers applying the given rounding mode.
+fn div_rounded_int<T: PrimInt>(lhs: T, rhs: T, rounding: Rounding) -> T {
+    let quotient = lhs / rhs;
+    let remainder = lhs % rhs;
+    if remainder.is_zero() {
+        return quotient;
+    }
+
+    // `quotient` is truncated toward zero, the exact value lies between it and `away`.
+    let negative = (remainder < T::zero()) != (rhs < T::zero());
+    let away = if negative {
+        quotient - T::one()
+    } else {
+        quotient + T::one()
+    };
+
+    let abs = |value: T| {
+        if value < T::zero() {
+            T::zero() - value
+        } else {
+            value
+        }
+    };
+    match rounding {
+        Rounding::Floor if negative => away,
+        Rounding::Floor => quotient,
+        Rounding::Ceil if negative => quotient,
+        Rounding::Ceil => away,
+        Rounding::HalfEven => {
+            let remainder = abs(remainder);
+            let rest = abs(rhs) - remainder;
+            let two = T::one() + T::one();
+            if remainder > rest || (remainder == rest && !(quotient % two).is_zero()) {
+                away
+            } else {
+                quotient
+            }
+        }
+    }
+}
+
+/// Computes `a * b / c` for integers of any width, see [`mul_div_wide`].
+fn mul_div_int<T: PrimInt>(a: T, b: T, c: T, rounding: Rounding) -> T {
+    assert!(!c.is_zero(), "attempt to divide by zero");
+    let magnitude = |value: T| match value.to_u128() {
+        Some(value) => value,
+        None => value.to_i128().unwrap().unsigned_abs(),
+    };
+    let negative = (a < T::zero()) ^ (b < T::zero()) ^ (c < T::zero());
+
+    mul_div_wide(
+        magnitude(a),
+        magnitude(b),
+        magnitude(c),
+        negative,
+        Some(rounding),
+    )
+    .and_then(|quotient| {
+        if negative {
+            0i128.checked_sub_unsigned(quotient).and_then(T::from)
+        } else {
+            T::from(quotient)
+        }
+    })
+    .expect("attempt to multiply with overflow")
+}
+
+macro_rules! impl_resource_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Resource for $t {
+                fn div_rounded(self, rhs: Self, rounding: Rounding) -> Self {
+                    div_rounded_int(self, rhs, rounding)
+                }
+
+                fn mul_div(self, mul: Self, div: Self, rounding: Rounding) -> Self {
+                    mul_div_int(self, mul, div, rounding)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_resource_for_float {
+    ($($t:ty),*) => {
+        $(
+            impl Resource for $t {
+                fn div_rounded(self, rhs: Self, _rounding: Rounding) -> Self {
+                    self / rhs
+                }
+            }
+        )*
+    };
+}
+
+impl_resource_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_resource_for_float!(f32, f64);
+
+/// Result of splitting an amount of resources between several recipients.
+///
+/// The sum of all `shares` and `dust` is always equal to the allocated amount.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Allocation<R> {
+    /// Amounts assigned to recipients, in the order of the given weights.
+    pub shares: Vec<R>,
+    /// Remainder which could not be assigned to any recipient because of rounding.
+    pub dust: R,
+}
+
+/// Splits `amount` proportionally to `weights` rounding each share with `rounding`.
+///
+/// Shares are assigned in order and never exceed what is left of `amount`, so rounding up can
+/// only shorten the last recipients. Anything left unassigned is returned as `dust`; if the
+/// weights sum to zero the whole `amount` becomes dust.
+pub fn allocate<R: Resource>(amount: R, weights: &[R], rounding: Rounding) -> Allocation<R> {
+    let total = weights.iter().fold(R::zero(), |acc, weight| acc + *weight);
+    if total.is_zero() {
+        return Allocation {
+            shares: vec![R::zero(); weights.len()],
+            dust: amount,
+        };
+    }
+
+    let mut remaining = amount;
+    let shares = weights
+        .iter()
+        .map(|weight| {
+            let share = amount.mul_div(*weight, total, rounding);
+            let share = if share > remaining { remaining } else { share };
+            remaining = remaining - share;
+            share
+        })
+        .collect();
+
+    Allocation {
+        shares,
+        dust: remaining,
+    }
+}
+
+/// Signed fixed-point number with `DECIMALS` digits after the decimal point.
+///
+/// Values are stored as an integer count of the smallest unit (`10^-DECIMALS`), so addition and
+/// subtraction are exact and division truncates toward zero at the last decimal place.
+///
+/// # Example:
+/// ```
+/// let amount: Fixed<2> = "12.34".parse().unwrap();
+/// assert_eq!(amount.raw(), 1234);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed<const DECIMALS: u32>(i128);
+
+impl<const DECIMALS: u32> Fixed<DECIMALS> {
+    /// Number of smallest units in one whole unit.
+    pub const SCALE: i128 = 10i128.pow(DECIMALS);
+
+    /// Creates a value from a count of the smallest units.
+    pub const fn from_raw(raw: i128) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the value as a count of the smallest units.
+    pub const fn raw(&self) -> i128 {
+        self.0
+    }
+}
+
+impl<const DECIMALS: u32> Add for Fixed<DECIMALS> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl<const DECIMALS: u32> Sub for Fixed<DECIMALS> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl<const DECIMALS: u32> Mul for Fixed<DECIMALS> {
+    type Output = Self;
+
+    /// # Panics
+    /// If the product does not fit the type.
+    fn mul(self, rhs: Self) -> Self {
+        Self(mul_div(self.0, rhs.0, Self::SCALE, None).expect("attempt to multiply with overflow"))
+    }
+}
+
+impl<const DECIMALS: u32> Div for Fixed<DECIMALS> {
+    type Output = Self;
+
+    /// # Panics
+    /// If `rhs` is zero or the quotient does not fit the type.
+    fn div(self, rhs: Self) -> Self {
+        assert!(rhs.0 != 0, "attempt to divide by zero");
+        Self(mul_div(self.0, Self::SCALE, rhs.0, None).expect("attempt to divide with overflow"))
+    }
+}
+
+impl<const DECIMALS: u32> Rem for Fixed<DECIMALS> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        Self(self.0 % rhs.0)
+    }
+}
+
+impl<const DECIMALS: u32> Zero for Fixed<DECIMALS> {
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const DECIMALS: u32> One for Fixed<DECIMALS> {
+    fn one() -> Self {
+        Self(Self::SCALE)
+    }
+}
+
+/// Error returned when a string can not be parsed into a [`Fixed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFixedError;
+
+impl Display for ParseFixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid fixed-point number")
+    }
+}
+
+impl<const DECIMALS: u32> Num for Fixed<DECIMALS> {
+    type FromStrRadixErr = ParseFixedError;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseFixedError> {
+        if radix != 10 {
+            return Err(ParseFixedError);
+        }
+
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if whole.is_empty() && fraction.is_empty() || fraction.len() > DECIMALS as usize {
+            return Err(ParseFixedError);
+        }
+
+        let parse = |part: &str| -> Result<i128, ParseFixedError> {
+            if part.is_empty() {
+                return Ok(0);
+            }
+            if !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ParseFixedError);
+            }
+            part.parse().map_err(|_| ParseFixedError)
+        };
+
+        let padding = 10i128.pow(DECIMALS - fraction.len() as u32);
+        let fraction = parse(fraction)? * padding;
+        let raw = parse(whole)?
+            .checked_mul(Self::SCALE)
+            .and_then(|whole| whole.checked_add(fraction))
+            .ok_or(ParseFixedError)?;
+        Ok(Self(if negative { -raw } else { raw }))
+    }
+}
+
+impl<const DECIMALS: u32> FromStr for Fixed<DECIMALS> {
+    type Err = ParseFixedError;
+
+    fn from_str(s: &str) -> Result<Self, ParseFixedError> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+impl<const DECIMALS: u32> FromPrimitive for Fixed<DECIMALS> {
+    fn from_i64(n: i64) -> Option<Self> {
+        (n as i128).checked_mul(Self::SCALE).map(Self)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        (n as i128).checked_mul(Self::SCALE).map(Self)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        let raw = (n * Self::SCALE as f64).trunc();
+        (raw.is_finite() && raw.abs() < i128::MAX as f64).then_some(Self(raw as i128))
+    }
+}
+
+impl<const DECIMALS: u32> ToPrimitive for Fixed<DECIMALS> {
+    fn to_i64(&self) -> Option<i64> {
+        (self.0 / Self::SCALE).to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        (self.0 / Self::SCALE).to_u64()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.0 as f64 / Self::SCALE as f64)
+    }
+}
+
+impl<const DECIMALS: u32> Resource for Fixed<DECIMALS> {
+    fn div_rounded(self, rhs: Self, rounding: Rounding) -> Self {
+        assert!(rhs.0 != 0, "attempt to divide by zero");
+        Self(
+            mul_div(self.0, Self::SCALE, rhs.0, Some(rounding))
+                .expect("attempt to divide with overflow"),
+        )
+    }
+
+    fn mul_div(self, mul: Self, div: Self, rounding: Rounding) -> Self {
+        assert!(div.0 != 0, "attempt to divide by zero");
+        Self(
+            mul_div(self.0, mul.0, div.0, Some(rounding))
+                .expect("attempt to multiply with overflow"),
+        )
+    }
+}
+
+/// Computes `a * b / c` without overflowing in the intermediate product.
+///
+/// The quotient is rounded with `rounding`, or truncated toward zero if it is `None`.
+/// Returns `None` if the quotient does not fit `i128`. `c` must not be zero.
+fn mul_div(a: i128, b: i128, c: i128, rounding: Option<Rounding>) -> Option<i128> {
+    let negative = (a < 0) ^ (b < 0) ^ (c < 0);
+    let quotient = mul_div_wide(
+        a.unsigned_abs(),
+        b.unsigned_abs(),
+        c.unsigned_abs(),
+        negative,
+        rounding,
+    )?;
+
+    if negative {
+        0i128.checked_sub_unsigned(quotient)
+    } else {
+        i128::try_from(quotient).ok()
+    }
+}
+
+/// Computes the magnitude of `a * b / c` through a 256-bit product, where `negative` is the sign
+/// of the exact result and decides the direction of `rounding`.
+///
+/// Returns `None` if the magnitude does not fit `u128`. `c` must not be zero.
+fn mul_div_wide(
+    a: u128,
+    b: u128,
+    c: u128,
+    negative: bool,
+    rounding: Option<Rounding>,
+) -> Option<u128> {
+    // 256-bit product as two 128-bit halves.
+    let mask = u128::from(u64::MAX);
+    let (a_hi, a_lo, b_hi, b_lo) = (a >> 64, a & mask, b >> 64, b & mask);
+    let low = a_lo * b_lo;
+    let middle = (low >> 64) + ((a_hi * b_lo) & mask) + ((a_lo * b_hi) & mask);
+    let high = a_hi * b_hi + ((a_hi * b_lo) >> 64) + ((a_lo * b_hi) >> 64) + (middle >> 64);
+    let low = (middle << 64) | (low & mask);
+    if high >= c {
+        return None;
+    }
+
+    let (mut quotient, mut remainder) = (low / c, low % c);
+    if high != 0 {
+        // Long division of the 256-bit product, one bit at a time.
+        (quotient, remainder) = (0, high);
+        for bit in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((low >> bit) & 1);
+            quotient <<= 1;
+            if carry == 1 || remainder >= c {
+                remainder = remainder.wrapping_sub(c);
+                quotient |= 1;
+            }
+        }
+    }
+
+    let away = remainder != 0
+        && match rounding {
+            None => false,
+            Some(Rounding::Floor) => negative,
+            Some(Rounding::Ceil) => !negative,
+            Some(Rounding::HalfEven) => {
+                let rest = c - remainder;
+                remainder > rest || (remainder == rest && quotient % 2 == 1)
+            }
+        };
+    if away {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+impl<const DECIMALS: u32> Display for Fixed<DECIMALS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let whole = (self.0 / Self::SCALE).unsigned_abs();
+        let fraction = (self.0 % Self::SCALE).unsigned_abs();
+        if DECIMALS == 0 {
+            write!(f, "{sign}{whole}")
+        } else {
+            write!(
+                f,
+                "{sign}{whole}.{fraction:0width$}",
+                width = DECIMALS as usize
+            )
+        }
+    }
+}
+
+impl<const DECIMALS: u32> Debug for Fixed<DECIMALS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// [`Fixed`] is stored as its decimal string, so values round-trip exactly.
+#[cfg(feature = "persistence")]
+impl<const DECIMALS: u32> serde::Serialize for Fixed<DECIMALS> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl<'de, const DECIMALS: u32> serde::Deserialize<'de> for Fixed<DECIMALS> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,160 @@
+//! Module defines [`SharedCompany`], a thread safe handle to a [`Company`] shared between many
+//! threads.
+//!
+//! # Lock ordering
+//! A shared company is guarded by a single [`RwLock`]. Queries take the read lock and run in
+//! parallel, mutations take the write lock and are serialized. Operations touching several
+//! staff members, like [`SharedCompany::transfer`] or [`SharedCompany::fire`], take the same
+//! single lock, so there is no per-member lock order to follow and they can not deadlock each
+//! other.
+//!
+//! The most common operations are forwarded by [`SharedCompany`] itself, the whole [`Company`]
+//! API is reached through the guards returned by [`SharedCompany::read`] and
+//! [`SharedCompany::write`].
+//!
+//! Two rules remain:
+//! - Never call methods of a [`SharedCompany`] while holding a guard of the same company, the
+//!   lock is not reentrant.
+//! - When locking several companies at once, always lock them in the same order, for example by
+//!   comparing their CEO IDs.
+
+use crate::company::Company;
+use crate::errors::Error;
+use crate::operation::Operation;
+use crate::resource::Resource;
+use crate::storage::{Backend, PersistentBackend};
+use crate::traits::StaffEntity;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Thread safe handle to a [`Company`].
+///
+/// Cloning the handle is cheap, all clones refer to the same company.
+pub struct SharedCompany<V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    company: Arc<RwLock<Company<V, R, B>>>,
+}
+
+impl<V: StaffEntity, R: Resource, B: Backend<V::ID>> Clone for SharedCompany<V, R, B> {
+    fn clone(&self) -> Self {
+        Self {
+            company: Arc::clone(&self.company),
+        }
+    }
+}
+
+impl<V: StaffEntity, R: Resource, B: Backend<V::ID>> From<Company<V, R, B>>
+    for SharedCompany<V, R, B>
+{
+    fn from(company: Company<V, R, B>) -> Self {
+        Self {
+            company: Arc::new(RwLock::new(company)),
+        }
+    }
+}
+
+impl<V: StaffEntity, R: Resource> SharedCompany<V, R> {
+    /// Wraps `company` to share it between threads.
+    ///
+    /// Companies of other backends are wrapped with [`SharedCompany::from`].
+    pub fn new(company: Company<V, R>) -> Self {
+        Self::from(company)
+    }
+}
+
+impl<V: StaffEntity, R: Resource, B: Backend<V::ID>> SharedCompany<V, R, B> {
+    /// Takes the read lock returning a guard with shared access to the company.
+    ///
+    /// # Panics
+    /// If another thread panicked while holding the write lock.
+    pub fn read(&self) -> RwLockReadGuard<'_, Company<V, R, B>> {
+        self.company.read().expect("company lock is poisoned")
+    }
+
+    /// Takes the write lock returning a guard with exclusive access to the company.
+    ///
+    /// # Panics
+    /// If another thread panicked while holding the write lock.
+    pub fn write(&self) -> RwLockWriteGuard<'_, Company<V, R, B>> {
+        self.company.write().expect("company lock is poisoned")
+    }
+
+    /// See [`Company::get_all_staff`].
+    pub fn get_all_staff(&self) -> Vec<V::ID> {
+        self.read().get_all_staff()
+    }
+
+    /// See [`Company::resource`].
+    pub fn resource(&self, staff_id: &V::ID) -> Option<R> {
+        self.read().resource(staff_id).copied()
+    }
+
+    /// See [`Company::available`].
+    pub fn available(&self, staff_id: &V::ID) -> Option<R> {
+        self.read().available(staff_id)
+    }
+
+    /// See [`Company::supervisor`].
+    pub fn supervisor(&self, staff_id: &V::ID) -> Option<V::ID> {
+        self.read().supervisor(staff_id).cloned()
+    }
+
+    /// See [`Company::subordinates_in_order`].
+    pub fn subordinates(&self, staff_id: &V::ID) -> Option<Vec<V::ID>> {
+        self.read().subordinates_in_order(staff_id)
+    }
+
+    /// See [`Company::total_supply`].
+    pub fn total_supply(&self) -> R {
+        self.read().total_supply()
+    }
+
+    /// See [`Company::mint`].
+    pub fn mint(&self, amount: R) {
+        self.write().mint(amount)
+    }
+
+    /// See [`Company::withdraw`].
+    pub fn withdraw(&self, staff_id: &V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        self.write().withdraw(staff_id, amount)
+    }
+
+    /// See [`Company::hire`].
+    pub fn hire(&self, staff: V, supervisor_id: &V::ID) -> Result<V::ID, Error<V::ID, R>> {
+        self.write().hire(staff, supervisor_id)
+    }
+
+    /// See [`Company::fire`].
+    pub fn fire(&self, staff_id: &V::ID) -> Result<V, Error<V::ID, R>> {
+        self.write().fire(staff_id)
+    }
+
+    /// See [`Company::change_supervisor`].
+    pub fn change_supervisor(
+        &self,
+        staff_id: &V::ID,
+        supervisor_id: &V::ID,
+    ) -> Result<(), Error<V::ID, R>> {
+        self.write().change_supervisor(staff_id, supervisor_id)
+    }
+
+    /// See [`Company::transfer`].
+    pub fn transfer(&self, from: &V::ID, to: &V::ID, amount: R) -> Result<(), Error<V::ID, R>> {
+        self.write().transfer(from, to, amount)
+    }
+
+    /// See [`Company::apply`].
+    pub fn apply(&self, operation: Operation<V, R>) -> Result<(), Error<V::ID, R>> {
+        self.write().apply(operation)
+    }
+
+    /// Consumes the handle returning the company if no other clones of the handle exist.
+    ///
+    /// # Returns
+    /// - `Ok(Company)` if this is the last handle.
+    /// - `Err(Self)` otherwise.
+    pub fn try_into_inner(self) -> Result<Company<V, R, B>, Self> {
+        match Arc::try_unwrap(self.company) {
+            Ok(lock) => Ok(lock.into_inner().expect("company lock is poisoned")),
+            Err(company) => Err(Self { company }),
+        }
+    }
+}
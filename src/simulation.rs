@@ -0,0 +1,212 @@
+//! Module defines the [`Simulation`] engine which drives a [`Company`] through discrete ticks.
+//!
+//! Every staff member is controlled by an [`Agent`] deciding its [`Action`]s each tick. Agents
+//! act one after another ordered by staff ID and share a seeded [`ChaCha8Rng`], so a simulation
+//! is fully deterministic given the seed.
+
+use crate::company::Company;
+use crate::resource::Resource;
+use crate::storage::{Backend, PersistentBackend};
+use crate::traits::StaffEntity;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::BTreeMap;
+
+/// Action a staff member takes during a tick.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action<V: StaffEntity, R> {
+    /// Hire a new subordinate.
+    Hire(V),
+    /// Transfer resources to another staff member.
+    Transfer { to: V::ID, amount: R },
+    /// Leave the company, see [`Company::fire`].
+    Quit,
+}
+
+/// Decision maker controlling a single staff member.
+pub trait Agent<V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    /// Returns actions of the staff member `staff_id` for the current tick.
+    ///
+    /// Actions are applied in order right after the call, so agents acting later in the same
+    /// tick see their effect.
+    fn act(
+        &mut self,
+        staff_id: &V::ID,
+        company: &Company<V, R, B>,
+        rng: &mut ChaCha8Rng,
+    ) -> Vec<Action<V, R>>;
+}
+
+/// Metrics collected at the end of a tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TickMetrics<R> {
+    /// Number of the tick, starting from `1`.
+    pub tick: u64,
+    /// Number of staff members.
+    pub headcount: usize,
+    /// Number of levels below the CEO.
+    pub depth: usize,
+    /// Balances of all staff members in ascending order.
+    pub balances: Vec<R>,
+    /// Number of actions applied during the tick.
+    pub applied_actions: usize,
+    /// Number of actions which failed during the tick.
+    pub failed_actions: usize,
+}
+
+/// Factory creating an agent for a staff member joining the simulation.
+pub type Spawn<V, R, B = PersistentBackend> =
+    Box<dyn FnMut(&V, &mut ChaCha8Rng) -> Box<dyn Agent<V, R, B>>>;
+
+/// Simulation engine driving a [`Company`] through discrete ticks.
+pub struct Simulation<V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    company: Company<V, R, B>,
+    rng: ChaCha8Rng,
+    tick: u64,
+    agents: BTreeMap<V::ID, Box<dyn Agent<V, R, B>>>,
+    spawn: Spawn<V, R, B>,
+    metrics: Vec<TickMetrics<R>>,
+}
+
+impl<V, R, B> Simulation<V, R, B>
+where
+    V: StaffEntity,
+    V::ID: Ord,
+    R: Resource,
+    B: Backend<V::ID>,
+{
+    /// Creates a simulation of `company` seeded with `seed`.
+    ///
+    /// `spawn` creates an agent for every current staff member and later for every hired one.
+    pub fn new(company: Company<V, R, B>, seed: u64, mut spawn: Spawn<V, R, B>) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        let mut ids = company.get_all_staff();
+        ids.sort();
+        let agents = ids
+            .into_iter()
+            .map(|id| {
+                let agent = spawn(company.get(&id).unwrap(), &mut rng);
+                (id, agent)
+            })
+            .collect();
+
+        Self {
+            company,
+            rng,
+            tick: 0,
+            agents,
+            spawn,
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Returns the simulated company.
+    pub fn company(&self) -> &Company<V, R, B> {
+        &self.company
+    }
+
+    /// Consumes the simulation returning the company.
+    pub fn into_company(self) -> Company<V, R, B> {
+        self.company
+    }
+
+    /// Returns the number of ticks run so far.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns metrics of all ticks run so far.
+    pub fn metrics(&self) -> &[TickMetrics<R>] {
+        &self.metrics
+    }
+
+    /// Runs a single tick: every staff member employed at the start of the tick acts once.
+    ///
+    /// # Returns
+    /// - Metrics of the tick.
+    pub fn step(&mut self) -> &TickMetrics<R> {
+        self.tick += 1;
+        let mut applied_actions = 0;
+        let mut failed_actions = 0;
+
+        let ids: Vec<V::ID> = self.agents.keys().cloned().collect();
+        for id in ids {
+            let agent = self.agents.get_mut(&id).unwrap();
+            for action in agent.act(&id, &self.company, &mut self.rng) {
+                if self.apply(&id, action) {
+                    applied_actions += 1;
+                } else {
+                    failed_actions += 1;
+                }
+            }
+        }
+
+        let metrics = self.collect_metrics(applied_actions, failed_actions);
+        self.metrics.push(metrics);
+        self.metrics.last().unwrap()
+    }
+
+    /// Runs `ticks` ticks.
+    pub fn run(&mut self, ticks: u64) {
+        for _ in 0..ticks {
+            self.step();
+        }
+    }
+
+    fn apply(&mut self, staff_id: &V::ID, action: Action<V, R>) -> bool {
+        match action {
+            Action::Hire(staff) => {
+                let agent = (self.spawn)(&staff, &mut self.rng);
+                match self.company.hire(staff, staff_id) {
+                    Ok(id) => {
+                        self.agents.insert(id, agent);
+                        true
+                    }
+                    Err(_) => false,
+                }
+            }
+            Action::Transfer { to, amount } => self.company.transfer(staff_id, &to, amount).is_ok(),
+            Action::Quit => {
+                let quit = self.company.fire(staff_id).is_ok();
+                if quit {
+                    self.agents.remove(staff_id);
+                }
+                quit
+            }
+        }
+    }
+
+    fn collect_metrics(&self, applied_actions: usize, failed_actions: usize) -> TickMetrics<R> {
+        let ids = self.company.get_all_staff();
+
+        let depth = ids
+            .iter()
+            .map(|id| {
+                let mut depth = 0;
+                let mut current = id;
+                while let Some(supervisor_id) = self.company.supervisor(current) {
+                    depth += 1;
+                    current = supervisor_id;
+                }
+                depth
+            })
+            .max()
+            .unwrap_or(0);
+
+        let mut balances: Vec<R> = ids
+            .iter()
+            .map(|id| *self.company.resource(id).unwrap())
+            .collect();
+        balances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        TickMetrics {
+            tick: self.tick,
+            headcount: ids.len(),
+            depth,
+            balances,
+            applied_actions,
+            failed_actions,
+        }
+    }
+}
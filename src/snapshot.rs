@@ -0,0 +1,39 @@
+//! Module defines [`Snapshot`]s of a company taken with [`Company::snapshot`] and restored with
+//! [`Company::restore`].
+
+use crate::company::Company;
+use crate::resource::Resource;
+use crate::storage::{Backend, PersistentBackend};
+use crate::traits::StaffEntity;
+
+/// Frozen state of a company.
+///
+/// A snapshot shares the whole state with the company it was taken from, so taking one is cheap.
+/// When the company changes afterwards, only the changed parts are copied with the default
+/// [`PersistentBackend`]; other backends copy every changed map once.
+#[derive(Clone)]
+pub struct Snapshot<V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    company: Company<V, R, B>,
+}
+
+impl<V: StaffEntity, R: Resource, B: Backend<V::ID>> Snapshot<V, R, B> {
+    /// Returns the company as it was when the snapshot was taken.
+    pub fn company(&self) -> &Company<V, R, B> {
+        &self.company
+    }
+}
+
+impl<V: StaffEntity + Clone, R: Resource, B: Backend<V::ID>> Company<V, R, B> {
+    /// Takes a snapshot of the whole company state: staff, balances, hierarchy, reservations,
+    /// limits, pending requests and settings.
+    pub fn snapshot(&self) -> Snapshot<V, R, B> {
+        Snapshot {
+            company: self.clone(),
+        }
+    }
+
+    /// Restores the company to the state of `snapshot`, discarding all changes made since.
+    pub fn restore(&mut self, snapshot: Snapshot<V, R, B>) {
+        *self = snapshot.company;
+    }
+}
@@ -0,0 +1,458 @@
+//! Module defines [`SqliteCompany`], a [`Company`] stored in an SQLite database and updated
+//! incrementally after every mutation.
+//!
+//! The company is mapped to tables:
+//! - `company`: a single row with the rounding mode and the supply counters.
+//! - `staff`: staff members.
+//! - `balances`: the balance of every staff member.
+//! - `reporting_lines`: the supervisor of every staff member except the CEO.
+//! - `ledger`: every applied [`Operation`] in order.
+//!
+//! Staff, IDs and resources are stored as JSON text on purpose: any serializable types can be
+//! used and values such as `u128` balances, [`crate::Fixed`] amounts or UUIDs are stored exactly,
+//! while an SQLite `INTEGER` holds only an `i64`. IDs are compared by their JSON text, and
+//! balances must be decoded before computing with them in SQL.
+//! As with [`crate::wal`], limits, reservations, approvals, structural rules and the staff order
+//! are not stored, so [`SqliteCompany::create`] rejects a company carrying any of them.
+//!
+//! Both [`SqliteCompany::create`] and [`SqliteCompany::open`] enable foreign keys on the
+//! connection, so deleting a staff member deletes its balance and reporting line.
+
+use crate::company::Company;
+use crate::errors::Error;
+use crate::operation::Operation;
+use crate::resource::{Resource, Rounding};
+use crate::storage::{Backend, PersistentBackend, StaffSet};
+use crate::traits::StaffEntity;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+pub use rusqlite;
+
+const FOREIGN_KEYS: &str = "PRAGMA foreign_keys = ON";
+
+const SCHEMA: &str = "
+    CREATE TABLE company (
+        id INTEGER PRIMARY KEY CHECK (id = 0),
+        rounding TEXT NOT NULL,
+        minted TEXT NOT NULL,
+        burned TEXT NOT NULL,
+        dust TEXT NOT NULL
+    );
+    CREATE TABLE staff (
+        id TEXT PRIMARY KEY,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE balances (
+        staff_id TEXT PRIMARY KEY REFERENCES staff (id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED,
+        balance TEXT NOT NULL
+    );
+    CREATE TABLE reporting_lines (
+        staff_id TEXT PRIMARY KEY REFERENCES staff (id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED,
+        supervisor_id TEXT NOT NULL REFERENCES staff (id) DEFERRABLE INITIALLY DEFERRED
+    );
+    CREATE TABLE ledger (
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        operation TEXT NOT NULL
+    );
+";
+
+/// Error of a [`SqliteCompany`].
+#[derive(Debug)]
+pub enum StorageError<ID, R> {
+    /// The operation was rejected by the company, the database was not changed.
+    Company(Error<ID, R>),
+
+    /// The database query failed.
+    Sqlite(rusqlite::Error),
+
+    /// A value can not be encoded or decoded.
+    Json(serde_json::Error),
+
+    /// The stored data does not form a valid company.
+    Corrupted(String),
+
+    /// The company given to [`SqliteCompany::create`] carries reservations, pending requests,
+    /// limits, an approval policy, structural rules or a staff order, which are not stored.
+    UnpersistedState,
+}
+
+impl<ID, R> From<Error<ID, R>> for StorageError<ID, R> {
+    fn from(error: Error<ID, R>) -> Self {
+        StorageError::Company(error)
+    }
+}
+
+impl<ID, R> From<rusqlite::Error> for StorageError<ID, R> {
+    fn from(error: rusqlite::Error) -> Self {
+        StorageError::Sqlite(error)
+    }
+}
+
+impl<ID, R> From<serde_json::Error> for StorageError<ID, R> {
+    fn from(error: serde_json::Error) -> Self {
+        StorageError::Json(error)
+    }
+}
+
+/// Result of a [`SqliteCompany`] method.
+pub type StorageResult<T, V, R> = Result<T, StorageError<<V as StaffEntity>::ID, R>>;
+
+/// [`Company`] stored in an SQLite database, see the [module documentation](self).
+pub struct SqliteCompany<V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    company: Company<V, R, B>,
+    connection: Connection,
+}
+
+impl<V, R, B> SqliteCompany<V, R, B>
+where
+    V: StaffEntity + Clone + Serialize + DeserializeOwned,
+    V::ID: Serialize + DeserializeOwned,
+    R: Resource + Serialize + DeserializeOwned,
+    B: Backend<V::ID>,
+{
+    /// Creates the tables in an empty database and stores `company` in them.
+    ///
+    /// # Errors
+    /// - [`StorageError::UnpersistedState`] if the company carries state which is not stored, so
+    ///   it would differ after reopening.
+    /// - [`StorageError::Sqlite`] if the tables already exist or can not be written.
+    pub fn create(
+        mut connection: Connection,
+        company: Company<V, R, B>,
+    ) -> Result<Self, StorageError<V::ID, R>> {
+        if company.has_unpersisted_state() {
+            return Err(StorageError::UnpersistedState);
+        }
+        connection.execute_batch(FOREIGN_KEYS)?;
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(SCHEMA)?;
+        write_counters(&transaction, &company)?;
+        for staff_id in company.get_all_staff() {
+            write_staff(&transaction, &company, &staff_id)?;
+        }
+        transaction.commit()?;
+
+        Ok(Self {
+            company,
+            connection,
+        })
+    }
+
+    /// Loads the company stored in the database.
+    ///
+    /// # Errors
+    /// - [`StorageError::Sqlite`] if the tables can not be read.
+    /// - [`StorageError::Json`] if a stored value can not be decoded.
+    /// - [`StorageError::Corrupted`] if the stored hierarchy or balances are invalid.
+    pub fn open(connection: Connection) -> Result<Self, StorageError<V::ID, R>> {
+        let corrupted = |message: &str| StorageError::Corrupted(message.to_string());
+        connection.execute_batch(FOREIGN_KEYS)?;
+
+        let (rounding, minted, burned, dust): (String, String, String, String) = connection
+            .query_row(
+                "SELECT rounding, minted, burned, dust FROM company WHERE id = 0",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()?
+            .ok_or_else(|| corrupted("company row is missing"))?;
+
+        let mut staff: HashMap<String, V> = HashMap::new();
+        let mut statement = connection.prepare("SELECT id, data FROM staff")?;
+        for row in statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))? {
+            let (id, data): (String, String) = row?;
+            staff.insert(id, serde_json::from_str(&data)?);
+        }
+        drop(statement);
+
+        let mut balances: HashMap<String, R> = HashMap::new();
+        let mut statement = connection.prepare("SELECT staff_id, balance FROM balances")?;
+        for row in statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))? {
+            let (id, balance): (String, String) = row?;
+            balances.insert(id, serde_json::from_str(&balance)?);
+        }
+        drop(statement);
+
+        let mut subordinates: HashMap<String, Vec<String>> = HashMap::new();
+        let mut statement =
+            connection.prepare("SELECT staff_id, supervisor_id FROM reporting_lines")?;
+        for row in statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))? {
+            let (id, supervisor_id): (String, String) = row?;
+            subordinates.entry(supervisor_id).or_default().push(id);
+        }
+        drop(statement);
+
+        let reporting: HashSet<&String> = subordinates.values().flatten().collect();
+        let mut ceo_ids = staff.keys().filter(|id| !reporting.contains(id));
+        let ceo_key = match (ceo_ids.next(), ceo_ids.next()) {
+            (Some(id), None) => id.clone(),
+            _ => return Err(corrupted("company must have exactly one CEO")),
+        };
+        let reporting = reporting.len();
+
+        let mut balance = |id: &String| {
+            balances
+                .remove(id)
+                .ok_or_else(|| corrupted("staff member has no balance"))
+        };
+        let ceo = staff.remove(&ceo_key).unwrap();
+        let ceo_balance = balance(&ceo_key)?;
+        let mut company = Company::new(ceo).with_rounding(serde_json::from_str(&rounding)?);
+        company.mint(ceo_balance);
+
+        let mut order = vec![ceo_key];
+        let mut index = 0;
+        while index < order.len() {
+            let supervisor_key = order[index].clone();
+            let supervisor_id: V::ID = serde_json::from_str(&supervisor_key)?;
+            for id in subordinates.remove(&supervisor_key).unwrap_or_default() {
+                let member = staff
+                    .remove(&id)
+                    .ok_or_else(|| corrupted("reporting line of unknown staff member"))?;
+                let member_balance = balance(&id)?;
+                company.insert_staff(member, &supervisor_id, member_balance);
+                order.push(id);
+            }
+            index += 1;
+        }
+        if order.len() != reporting + 1 || !staff.is_empty() {
+            return Err(corrupted("staff members are not connected to the CEO"));
+        }
+
+        company.set_supply(
+            serde_json::from_str(&minted)?,
+            serde_json::from_str(&burned)?,
+            serde_json::from_str(&dust)?,
+        );
+        company
+            .check_supply()
+            .map_err(|_| corrupted("balances do not match the supply"))?;
+
+        Ok(Self {
+            company,
+            connection,
+        })
+    }
+
+    /// Returns the company.
+    pub fn company(&self) -> &Company<V, R, B> {
+        &self.company
+    }
+
+    /// Returns the database connection.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Consumes the storage returning the company and the connection.
+    pub fn into_parts(self) -> (Company<V, R, B>, Connection) {
+        (self.company, self.connection)
+    }
+
+    /// Applies an operation and stores the rows it changed together with a ledger entry in one
+    /// transaction.
+    ///
+    /// If storing fails the company is restored from a [`Company::snapshot`], so the state in
+    /// memory always matches the database.
+    ///
+    /// # Errors
+    /// - [`StorageError::Company`] if the operation fails.
+    /// - [`StorageError::Sqlite`] or [`StorageError::Json`] if the changes can not be stored.
+    pub fn apply(&mut self, operation: Operation<V, R>) -> Result<(), StorageError<V::ID, R>> {
+        let entry = serde_json::to_string(&operation)?;
+        let mut touched = match &operation {
+            Operation::Hire {
+                staff,
+                supervisor_id,
+            } => vec![staff.get_id(), supervisor_id.clone()],
+            Operation::Fire { staff_id } => {
+                let mut ids = vec![staff_id.clone()];
+                ids.extend(self.company.supervisor(staff_id).cloned());
+                ids.extend(
+                    self.company
+                        .subordinate_set(staff_id)
+                        .into_iter()
+                        .flat_map(StaffSet::iter)
+                        .cloned(),
+                );
+                ids
+            }
+            Operation::Transfer { from, to, .. } => vec![from.clone(), to.clone()],
+            Operation::Withdraw { staff_id, .. } => vec![staff_id.clone()],
+            Operation::Mint { .. } => vec![],
+            Operation::ChangeSupervisor { staff_id, .. } => vec![staff_id.clone()],
+        };
+        touched.extend(self.company.ceo().map(|ceo| ceo.get_id()));
+
+        let snapshot = self.company.snapshot();
+        self.company.apply(operation)?;
+
+        let result = (|| {
+            let transaction = self.connection.transaction()?;
+            transaction.execute("INSERT INTO ledger (operation) VALUES (?1)", [entry])?;
+            write_counters(&transaction, &self.company)?;
+            for staff_id in touched.iter() {
+                write_staff(&transaction, &self.company, staff_id)?;
+            }
+            transaction.commit()?;
+            Ok(())
+        })();
+        if result.is_err() {
+            self.company.restore(snapshot);
+        }
+        result
+    }
+
+    /// See [`Company::hire`].
+    pub fn hire(
+        &mut self,
+        staff: V,
+        supervisor_id: &V::ID,
+    ) -> Result<V::ID, StorageError<V::ID, R>> {
+        let staff_id = staff.get_id();
+        self.apply(Operation::Hire {
+            staff,
+            supervisor_id: supervisor_id.clone(),
+        })?;
+        Ok(staff_id)
+    }
+
+    /// See [`Company::fire`].
+    pub fn fire(&mut self, staff_id: &V::ID) -> Result<V, StorageError<V::ID, R>> {
+        let staff = self.company.get(staff_id).cloned();
+        self.apply(Operation::Fire {
+            staff_id: staff_id.clone(),
+        })?;
+        Ok(staff.unwrap())
+    }
+
+    /// See [`Company::transfer`].
+    pub fn transfer(
+        &mut self,
+        from: &V::ID,
+        to: &V::ID,
+        amount: R,
+    ) -> Result<(), StorageError<V::ID, R>> {
+        self.apply(Operation::Transfer {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        })
+    }
+
+    /// See [`Company::withdraw`].
+    pub fn withdraw(&mut self, staff_id: &V::ID, amount: R) -> Result<(), StorageError<V::ID, R>> {
+        self.apply(Operation::Withdraw {
+            staff_id: staff_id.clone(),
+            amount,
+        })
+    }
+
+    /// See [`Company::mint`].
+    pub fn mint(&mut self, amount: R) -> Result<(), StorageError<V::ID, R>> {
+        self.apply(Operation::Mint { amount })
+    }
+
+    /// See [`Company::change_supervisor`].
+    pub fn change_supervisor(
+        &mut self,
+        staff_id: &V::ID,
+        supervisor_id: &V::ID,
+    ) -> Result<(), StorageError<V::ID, R>> {
+        self.apply(Operation::ChangeSupervisor {
+            staff_id: staff_id.clone(),
+            supervisor_id: supervisor_id.clone(),
+        })
+    }
+
+    /// Returns all operations stored in the ledger in order.
+    ///
+    /// # Errors
+    /// - [`StorageError::Sqlite`] if the ledger can not be read.
+    /// - [`StorageError::Json`] if an entry can not be decoded.
+    pub fn ledger(&self) -> StorageResult<Vec<Operation<V, R>>, V, R> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT operation FROM ledger ORDER BY seq")?;
+        let entries = statement.query_map([], |row| row.get::<_, String>(0))?;
+        let mut operations = Vec::new();
+        for entry in entries {
+            operations.push(serde_json::from_str(&entry?)?);
+        }
+        Ok(operations)
+    }
+}
+
+fn write_counters<V, R, B>(
+    connection: &Connection,
+    company: &Company<V, R, B>,
+) -> Result<(), StorageError<V::ID, R>>
+where
+    V: StaffEntity,
+    R: Resource + Serialize,
+    B: Backend<V::ID>,
+{
+    let rounding: Rounding = company.rounding();
+    connection.execute(
+        "INSERT INTO company (id, rounding, minted, burned, dust) VALUES (0, ?1, ?2, ?3, ?4)
+         ON CONFLICT (id) DO UPDATE SET
+             rounding = excluded.rounding,
+             minted = excluded.minted,
+             burned = excluded.burned,
+             dust = excluded.dust",
+        params![
+            serde_json::to_string(&rounding)?,
+            serde_json::to_string(&company.total_minted())?,
+            serde_json::to_string(&company.total_burned())?,
+            serde_json::to_string(&company.dust())?,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Stores the rows of a staff member, or deletes them if it is no longer in the company.
+fn write_staff<V, R, B>(
+    connection: &Connection,
+    company: &Company<V, R, B>,
+    staff_id: &V::ID,
+) -> Result<(), StorageError<V::ID, R>>
+where
+    V: StaffEntity + Serialize,
+    V::ID: Serialize,
+    R: Resource + Serialize,
+    B: Backend<V::ID>,
+{
+    let id = serde_json::to_string(staff_id)?;
+    let Some(staff) = company.get(staff_id) else {
+        connection.execute("DELETE FROM reporting_lines WHERE staff_id = ?1", [&id])?;
+        connection.execute("DELETE FROM balances WHERE staff_id = ?1", [&id])?;
+        connection.execute("DELETE FROM staff WHERE id = ?1", [&id])?;
+        return Ok(());
+    };
+
+    connection.execute(
+        "INSERT INTO staff (id, data) VALUES (?1, ?2)
+         ON CONFLICT (id) DO UPDATE SET data = excluded.data",
+        params![id, serde_json::to_string(staff)?],
+    )?;
+    connection.execute(
+        "INSERT INTO balances (staff_id, balance) VALUES (?1, ?2)
+         ON CONFLICT (staff_id) DO UPDATE SET balance = excluded.balance",
+        params![
+            id,
+            serde_json::to_string(company.resource(staff_id).unwrap())?
+        ],
+    )?;
+    match company.supervisor(staff_id) {
+        Some(supervisor_id) => connection.execute(
+            "INSERT INTO reporting_lines (staff_id, supervisor_id) VALUES (?1, ?2)
+             ON CONFLICT (staff_id) DO UPDATE SET supervisor_id = excluded.supervisor_id",
+            params![id, serde_json::to_string(supervisor_id)?],
+        )?,
+        None => connection.execute("DELETE FROM reporting_lines WHERE staff_id = ?1", [&id])?,
+    };
+    Ok(())
+}
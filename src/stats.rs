@@ -0,0 +1,39 @@
+//! Module defines [`CompanyStats`] returned by [`crate::Company::stats`] describing the shape of
+//! the hierarchy and the distribution of resources between staff members.
+
+use std::collections::BTreeMap;
+
+/// Distribution of staff balances.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceStats<R> {
+    /// The smallest balance.
+    pub min: R,
+    /// The largest balance.
+    pub max: R,
+    /// The median balance, for an even headcount the mean of the two middle balances rounded
+    /// with the company [`crate::Rounding`].
+    pub median: R,
+    /// Gini coefficient of the balances, `0.0` when resources are split equally and approaching
+    /// `1.0` when a single staff member holds everything.
+    pub gini: f64,
+}
+
+/// Organisation statistics of a company.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompanyStats<ID, R> {
+    /// Number of staff members including the CEO.
+    pub headcount: usize,
+    /// Number of levels below the CEO.
+    pub max_depth: usize,
+    /// Average number of levels between a staff member and the CEO.
+    pub average_depth: f64,
+    /// Mapping from a number of direct reports to the number of managers having that many.
+    /// Staff members without subordinates are not included, see `leaves`.
+    pub span_of_control: BTreeMap<usize, usize>,
+    /// Number of staff members without subordinates.
+    pub leaves: usize,
+    /// Distribution of staff balances.
+    pub balances: BalanceStats<R>,
+    /// Managers with exactly one direct report, supervisors go before their subordinates.
+    pub single_report_managers: Vec<ID>,
+}
@@ -0,0 +1,442 @@
+//! Module defines the [`Backend`] trait choosing the maps and sets a [`crate::company::Company`]
+//! keeps its staff, balances and relations in, and the backends provided by the library.
+//!
+//! A backend is selected by the third type parameter of the company, which defaults to
+//! [`PersistentBackend`]:
+//! - [`PersistentBackend`]: [`im::HashMap`], copies share their structure, so snapshots stay
+//!   cheap while the company keeps changing. Sets of subordinates are [`HashSet`]s shared
+//!   between copies until they change.
+//! - [`HashBackend`]: [`HashMap`] and [`HashSet`], the fastest for arbitrary IDs, but a copy of a
+//!   changed map is a full copy.
+//! - [`BTreeBackend`]: [`BTreeMap`] and [`BTreeSet`], iterates in the order of IDs.
+//! - [`DenseBackend`]: [`DenseMap`], a vector indexed by small non-negative integer IDs, other
+//!   IDs are kept in a [`HashMap`]. Sets of subordinates are small, so they are [`HashSet`]s.
+//!
+//! Other backends are added by implementing [`StaffMap`], [`StaffSet`] and [`Backend`].
+
+use num_traits::ToPrimitive;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+
+/// Map from staff IDs to values of one kind.
+///
+/// Maps are cloned when a shared company is changed, see [`crate::company::Company`].
+pub trait StaffMap<ID, T>: Default + Clone {
+    /// Returns a reference to the value of the staff member.
+    fn get(&self, id: &ID) -> Option<&T>;
+
+    /// Returns a mutable reference to the value of the staff member.
+    fn get_mut(&mut self, id: &ID) -> Option<&mut T>;
+
+    /// Sets the value of the staff member returning the previous one.
+    fn insert(&mut self, id: ID, value: T) -> Option<T>;
+
+    /// Removes the value of the staff member returning it.
+    fn remove(&mut self, id: &ID) -> Option<T>;
+
+    /// Returns the number of staff members with a value.
+    fn len(&self) -> usize;
+
+    /// Returns all staff members with their values in the order of the map.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a ID, &'a T)>
+    where
+        ID: 'a,
+        T: 'a;
+
+    /// Returns whether the staff member has a value.
+    fn contains_key(&self, id: &ID) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Returns whether the map is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns all staff members with a value in the order of the map.
+    fn keys<'a>(&'a self) -> impl Iterator<Item = &'a ID>
+    where
+        ID: 'a,
+        T: 'a,
+    {
+        self.iter().map(|(id, _)| id)
+    }
+
+    /// Returns all values in the order of the map.
+    fn values<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        ID: 'a,
+        T: 'a,
+    {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns a mutable reference to the value of the staff member, inserting the default value
+    /// first if there is none.
+    fn get_or_default(&mut self, id: &ID) -> &mut T
+    where
+        ID: Clone,
+        T: Default,
+    {
+        if !self.contains_key(id) {
+            self.insert(id.clone(), T::default());
+        }
+        self.get_mut(id).unwrap()
+    }
+}
+
+/// Set of staff IDs, holding the subordinates of a staff member.
+pub trait StaffSet<ID>: Default + Clone {
+    /// Adds the staff member returning whether it was not in the set yet.
+    fn insert(&mut self, id: ID) -> bool;
+
+    /// Removes the staff member returning whether it was in the set.
+    fn remove(&mut self, id: &ID) -> bool;
+
+    /// Returns whether the staff member is in the set.
+    fn contains(&self, id: &ID) -> bool;
+
+    /// Returns the number of staff members in the set.
+    fn len(&self) -> usize;
+
+    /// Returns all staff members in the order of the set.
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a ID>
+    where
+        ID: 'a;
+
+    /// Returns whether the set is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Family of [`StaffMap`]s and the [`StaffSet`] used by a company with IDs of type `ID`.
+pub trait Backend<ID> {
+    /// The map holding values of type `T`.
+    type Map<T: Clone>: StaffMap<ID, T>;
+
+    /// The set holding the subordinates of a staff member.
+    type Set: StaffSet<ID>;
+}
+
+/// Backend storing everything in persistent [`im::HashMap`]s. This is the default.
+///
+/// A changed copy of a map copies only the path to the changed value and shares the rest with
+/// the original. Sets of subordinates are [`HashSet`]s, so a changed copy copies only the set of
+/// the changed supervisor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PersistentBackend;
+
+impl<ID: Eq + Hash + Clone> Backend<ID> for PersistentBackend {
+    type Map<T: Clone> = im::HashMap<ID, T>;
+    type Set = HashSet<ID>;
+}
+
+impl<ID: Eq + Hash + Clone, T: Clone> StaffMap<ID, T> for im::HashMap<ID, T> {
+    fn get(&self, id: &ID) -> Option<&T> {
+        im::HashMap::get(self, id)
+    }
+
+    fn get_mut(&mut self, id: &ID) -> Option<&mut T> {
+        im::HashMap::get_mut(self, id)
+    }
+
+    fn insert(&mut self, id: ID, value: T) -> Option<T> {
+        im::HashMap::insert(self, id, value)
+    }
+
+    fn remove(&mut self, id: &ID) -> Option<T> {
+        im::HashMap::remove(self, id)
+    }
+
+    fn len(&self) -> usize {
+        im::HashMap::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a ID, &'a T)>
+    where
+        ID: 'a,
+        T: 'a,
+    {
+        im::HashMap::iter(self)
+    }
+}
+
+impl<ID: Eq + Hash + Clone> StaffSet<ID> for im::HashSet<ID> {
+    fn insert(&mut self, id: ID) -> bool {
+        im::HashSet::insert(self, id).is_none()
+    }
+
+    fn remove(&mut self, id: &ID) -> bool {
+        im::HashSet::remove(self, id).is_some()
+    }
+
+    fn contains(&self, id: &ID) -> bool {
+        im::HashSet::contains(self, id)
+    }
+
+    fn len(&self) -> usize {
+        im::HashSet::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a ID>
+    where
+        ID: 'a,
+    {
+        im::HashSet::iter(self)
+    }
+}
+
+/// Backend storing everything in [`HashMap`]s and [`HashSet`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HashBackend;
+
+impl<ID: Eq + Hash + Clone> Backend<ID> for HashBackend {
+    type Map<T: Clone> = HashMap<ID, T>;
+    type Set = HashSet<ID>;
+}
+
+impl<ID: Eq + Hash + Clone, T: Clone> StaffMap<ID, T> for HashMap<ID, T> {
+    fn get(&self, id: &ID) -> Option<&T> {
+        HashMap::get(self, id)
+    }
+
+    fn get_mut(&mut self, id: &ID) -> Option<&mut T> {
+        HashMap::get_mut(self, id)
+    }
+
+    fn insert(&mut self, id: ID, value: T) -> Option<T> {
+        HashMap::insert(self, id, value)
+    }
+
+    fn remove(&mut self, id: &ID) -> Option<T> {
+        HashMap::remove(self, id)
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a ID, &'a T)>
+    where
+        ID: 'a,
+        T: 'a,
+    {
+        HashMap::iter(self)
+    }
+}
+
+impl<ID: Eq + Hash + Clone> StaffSet<ID> for HashSet<ID> {
+    fn insert(&mut self, id: ID) -> bool {
+        HashSet::insert(self, id)
+    }
+
+    fn remove(&mut self, id: &ID) -> bool {
+        HashSet::remove(self, id)
+    }
+
+    fn contains(&self, id: &ID) -> bool {
+        HashSet::contains(self, id)
+    }
+
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a ID>
+    where
+        ID: 'a,
+    {
+        HashSet::iter(self)
+    }
+}
+
+/// Backend storing everything in [`BTreeMap`]s and [`BTreeSet`]s, so staff members are visited
+/// in the order of their IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BTreeBackend;
+
+impl<ID: Ord + Clone> Backend<ID> for BTreeBackend {
+    type Map<T: Clone> = BTreeMap<ID, T>;
+    type Set = BTreeSet<ID>;
+}
+
+impl<ID: Ord + Clone, T: Clone> StaffMap<ID, T> for BTreeMap<ID, T> {
+    fn get(&self, id: &ID) -> Option<&T> {
+        BTreeMap::get(self, id)
+    }
+
+    fn get_mut(&mut self, id: &ID) -> Option<&mut T> {
+        BTreeMap::get_mut(self, id)
+    }
+
+    fn insert(&mut self, id: ID, value: T) -> Option<T> {
+        BTreeMap::insert(self, id, value)
+    }
+
+    fn remove(&mut self, id: &ID) -> Option<T> {
+        BTreeMap::remove(self, id)
+    }
+
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a ID, &'a T)>
+    where
+        ID: 'a,
+        T: 'a,
+    {
+        BTreeMap::iter(self)
+    }
+}
+
+impl<ID: Ord + Clone> StaffSet<ID> for BTreeSet<ID> {
+    fn insert(&mut self, id: ID) -> bool {
+        BTreeSet::insert(self, id)
+    }
+
+    fn remove(&mut self, id: &ID) -> bool {
+        BTreeSet::remove(self, id)
+    }
+
+    fn contains(&self, id: &ID) -> bool {
+        BTreeSet::contains(self, id)
+    }
+
+    fn len(&self) -> usize {
+        BTreeSet::len(self)
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a ID>
+    where
+        ID: 'a,
+    {
+        BTreeSet::iter(self)
+    }
+}
+
+/// Backend storing everything in [`DenseMap`]s, for small non-negative integer IDs, and
+/// subordinates in [`HashSet`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DenseBackend;
+
+impl<ID: ToPrimitive + Eq + Hash + Clone> Backend<ID> for DenseBackend {
+    type Map<T: Clone> = DenseMap<ID, T>;
+    type Set = HashSet<ID>;
+}
+
+/// Number of slots a [`DenseMap`] starts with once a value is inserted.
+const MIN_SLOTS: usize = 64;
+
+/// Map keeping the value of a staff member in a vector slot at the index of its ID.
+///
+/// Lookups do not hash. The vector at most doubles to fit a new ID, IDs beyond that, negative
+/// IDs and IDs which do not fit `usize` are kept in a [`HashMap`] and visited after the others.
+/// Such IDs move to the vector once it grows past them, so dense IDs should be preferred.
+#[derive(Debug, Clone)]
+pub struct DenseMap<ID, T> {
+    slots: Vec<Option<(ID, T)>>,
+    filled: usize,
+    sparse: HashMap<ID, T>,
+}
+
+impl<ID, T> Default for DenseMap<ID, T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            filled: 0,
+            sparse: HashMap::new(),
+        }
+    }
+}
+
+impl<ID: ToPrimitive + Eq + Hash, T> DenseMap<ID, T> {
+    /// Returns the slot index of an ID, `None` if the ID is kept in the sparse map.
+    fn slot(&self, id: &ID) -> Option<usize> {
+        id.to_usize().filter(|&index| index < self.slots.len())
+    }
+
+    /// Doubles the slots if that fits `index`, moving the values of the new slots out of the
+    /// sparse map.
+    fn grow(&mut self, index: usize) -> bool {
+        let len = (2 * self.slots.len()).max(MIN_SLOTS);
+        if index >= len {
+            return false;
+        }
+
+        self.slots.resize_with(len, || None);
+        let sparse = std::mem::take(&mut self.sparse);
+        for (id, value) in sparse {
+            match self.slot(&id) {
+                Some(index) => {
+                    self.slots[index] = Some((id, value));
+                    self.filled += 1;
+                }
+                None => {
+                    self.sparse.insert(id, value);
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<ID: ToPrimitive + Eq + Hash + Clone, T: Clone> StaffMap<ID, T> for DenseMap<ID, T> {
+    fn get(&self, id: &ID) -> Option<&T> {
+        match self.slot(id) {
+            Some(index) => self.slots[index].as_ref().map(|(_, value)| value),
+            None => self.sparse.get(id),
+        }
+    }
+
+    fn get_mut(&mut self, id: &ID) -> Option<&mut T> {
+        match self.slot(id) {
+            Some(index) => self.slots[index].as_mut().map(|(_, value)| value),
+            None => self.sparse.get_mut(id),
+        }
+    }
+
+    fn insert(&mut self, id: ID, value: T) -> Option<T> {
+        let index = match self.slot(&id) {
+            Some(index) => index,
+            None => match id.to_usize() {
+                Some(index) if self.grow(index) => index,
+                _ => return self.sparse.insert(id, value),
+            },
+        };
+
+        let previous = self.slots[index]
+            .replace((id, value))
+            .map(|(_, value)| value);
+        if previous.is_none() {
+            self.filled += 1;
+        }
+        previous
+    }
+
+    fn remove(&mut self, id: &ID) -> Option<T> {
+        let Some(index) = self.slot(id) else {
+            return self.sparse.remove(id);
+        };
+        let (_, value) = self.slots[index].take()?;
+        self.filled -= 1;
+        Some(value)
+    }
+
+    fn len(&self) -> usize {
+        self.filled + self.sparse.len()
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a ID, &'a T)>
+    where
+        ID: 'a,
+        T: 'a,
+    {
+        self.slots
+            .iter()
+            .flatten()
+            .map(|(id, value)| (id, value))
+            .chain(self.sparse.iter())
+    }
+}
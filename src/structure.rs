@@ -0,0 +1,271 @@
+//! Module defines [`StructureRules`] restricting the shape of the hierarchy, the [`Violation`]s
+//! of them and the [`Move`]s suggested by [`crate::Company::rebalance_plan`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Structural rules of the hierarchy.
+///
+/// Every rule is optional, `None` means that the corresponding value is not restricted.
+/// Rules are checked by [`crate::Company::hire`] and [`crate::Company::change_supervisor`].
+/// [`crate::Company::fire`] never fails because of them, use [`crate::Company::rebalance`] to
+/// bring the hierarchy back within the rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StructureRules {
+    /// Maximum number of direct reports of a supervisor.
+    pub max_reports: Option<usize>,
+    /// Maximum number of levels below the CEO.
+    pub max_depth: Option<usize>,
+    /// Minimum number of direct reports for a staff member to stay a manager. The CEO is exempt.
+    pub min_reports: Option<usize>,
+}
+
+/// Kind of a structural rule reported by [`crate::errors::Error::StructureViolation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureRule {
+    /// [`StructureRules::max_reports`] is violated.
+    MaxReports,
+    /// [`StructureRules::max_depth`] is violated.
+    MaxDepth,
+    /// [`StructureRules::min_reports`] is violated.
+    MinReports,
+}
+
+/// Structural rule violated by the current hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation<ID> {
+    /// The staff member violating the rule: the supervisor for report counts, the staff member
+    /// itself for depth.
+    pub staff_id: ID,
+    pub rule: StructureRule,
+    /// The value of the rule.
+    pub limit: usize,
+    /// The actual number of reports or depth.
+    pub actual: usize,
+}
+
+/// Change of a staff member's supervisor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move<ID> {
+    pub staff_id: ID,
+    pub from: ID,
+    pub to: ID,
+}
+
+/// Ordered copy of the hierarchy used to check rules and plan moves deterministically.
+pub(crate) struct Tree<ID> {
+    root: ID,
+    supervisors: BTreeMap<ID, ID>,
+    subordinates: BTreeMap<ID, BTreeSet<ID>>,
+}
+
+impl<ID: Ord + Clone> Tree<ID> {
+    pub(crate) fn new<'a>(root: ID, supervisors: impl Iterator<Item = (&'a ID, &'a ID)>) -> Self
+    where
+        ID: 'a,
+    {
+        let supervisors: BTreeMap<ID, ID> = supervisors
+            .map(|(id, supervisor_id)| (id.clone(), supervisor_id.clone()))
+            .collect();
+        let mut subordinates: BTreeMap<ID, BTreeSet<ID>> = BTreeMap::new();
+        for (id, supervisor_id) in supervisors.iter() {
+            subordinates
+                .entry(supervisor_id.clone())
+                .or_default()
+                .insert(id.clone());
+        }
+
+        Self {
+            root,
+            supervisors,
+            subordinates,
+        }
+    }
+
+    /// Returns all violations, supervisors go before their subordinates.
+    pub(crate) fn violations(&self, rules: &StructureRules) -> Vec<Violation<ID>> {
+        let mut violations = Vec::new();
+        for id in self.order() {
+            let depth = self.depth(&id);
+            let reports = self.reports(&id);
+
+            if let Some(limit) = rules.max_depth.filter(|limit| depth > *limit) {
+                violations.push(Violation {
+                    staff_id: id.clone(),
+                    rule: StructureRule::MaxDepth,
+                    limit,
+                    actual: depth,
+                });
+            }
+            if let Some(limit) = rules.max_reports.filter(|limit| reports > *limit) {
+                violations.push(Violation {
+                    staff_id: id.clone(),
+                    rule: StructureRule::MaxReports,
+                    limit,
+                    actual: reports,
+                });
+            }
+            if let Some(limit) = rules.min_reports {
+                if id != self.root && reports > 0 && reports < limit {
+                    violations.push(Violation {
+                        staff_id: id.clone(),
+                        rule: StructureRule::MinReports,
+                        limit,
+                        actual: reports,
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// Moves staff members until the hierarchy is within `rules` or no further move helps, see
+    /// [`crate::Company::rebalance_plan`].
+    pub(crate) fn rebalance(&mut self, rules: &StructureRules) -> Vec<Move<ID>> {
+        let mut moves = Vec::new();
+
+        if let Some(max_depth) = rules.max_depth.filter(|limit| *limit > 0) {
+            for id in self.order() {
+                let depth = self.depth(&id);
+                if depth > max_depth {
+                    let mut to = self.supervisors[&id].clone();
+                    for _ in 0..depth - max_depth {
+                        to = self.supervisors[&to].clone();
+                    }
+                    self.move_to(&mut moves, id, to);
+                }
+            }
+        }
+
+        if let Some(max_reports) = rules.max_reports.filter(|limit| *limit > 0) {
+            for manager_id in self.order() {
+                while self.reports(&manager_id) > max_reports {
+                    let subordinates = &self.subordinates[&manager_id];
+                    let height = subordinates.iter().map(|id| self.height(id)).min().unwrap();
+                    let id = subordinates
+                        .iter()
+                        .rfind(|id| self.height(id) == height)
+                        .unwrap()
+                        .clone();
+
+                    // managers short of reports go first, then other managers, then the rest
+                    let rank = |target_id: &ID| match self.reports(target_id) {
+                        0 => 2,
+                        reports if rules.min_reports.is_some_and(|limit| reports < limit) => 0,
+                        _ => 1,
+                    };
+                    let target = subordinates
+                        .iter()
+                        .cloned()
+                        .chain(self.order())
+                        .filter(|target_id| {
+                            *target_id != manager_id
+                                && !self.is_within(target_id, &id)
+                                && self.reports(target_id) < max_reports
+                                && rules
+                                    .max_depth
+                                    .is_none_or(|limit| self.depth(target_id) + 1 + height <= limit)
+                        })
+                        .enumerate()
+                        .min_by_key(|(index, target_id)| (rank(target_id), *index))
+                        .map(|(_, target_id)| target_id);
+                    match target {
+                        Some(to) => self.move_to(&mut moves, id, to),
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        if let Some(min_reports) = rules.min_reports {
+            for manager_id in self.order().into_iter().rev() {
+                let reports = self.reports(&manager_id);
+                if manager_id == self.root || reports == 0 || reports >= min_reports {
+                    continue;
+                }
+
+                let supervisor_id = self.supervisors[&manager_id].clone();
+                let capacity = rules
+                    .max_reports
+                    .is_none_or(|limit| self.reports(&supervisor_id) + reports <= limit);
+                if capacity {
+                    for id in self.subordinates[&manager_id].clone() {
+                        self.move_to(&mut moves, id, supervisor_id.clone());
+                    }
+                }
+            }
+        }
+
+        moves
+    }
+
+    fn reports(&self, id: &ID) -> usize {
+        self.subordinates.get(id).map_or(0, |ids| ids.len())
+    }
+
+    fn depth(&self, id: &ID) -> usize {
+        let mut depth = 0;
+        let mut current = id;
+        while let Some(supervisor_id) = self.supervisors.get(current) {
+            depth += 1;
+            current = supervisor_id;
+        }
+        depth
+    }
+
+    /// Returns the number of levels of subordinates below `id`, visiting one level at a time.
+    fn height(&self, id: &ID) -> usize {
+        let mut height = 0;
+        let mut level = vec![id];
+        loop {
+            level = level
+                .into_iter()
+                .filter_map(|id| self.subordinates.get(id))
+                .flatten()
+                .collect();
+            if level.is_empty() {
+                return height;
+            }
+            height += 1;
+        }
+    }
+
+    /// Returns whether `id` is `root_id` or one of its direct or indirect subordinates.
+    fn is_within(&self, id: &ID, root_id: &ID) -> bool {
+        let mut current = id;
+        loop {
+            if current == root_id {
+                return true;
+            }
+            match self.supervisors.get(current) {
+                Some(supervisor_id) => current = supervisor_id,
+                None => return false,
+            }
+        }
+    }
+
+    /// Returns IDs of all staff members, supervisors go before their subordinates.
+    fn order(&self) -> Vec<ID> {
+        let mut order = vec![self.root.clone()];
+        let mut index = 0;
+        while index < order.len() {
+            if let Some(subordinates) = self.subordinates.get(&order[index]) {
+                order.extend(subordinates.iter().cloned());
+            }
+            index += 1;
+        }
+        order
+    }
+
+    fn move_to(&mut self, moves: &mut Vec<Move<ID>>, staff_id: ID, to: ID) {
+        let from = self
+            .supervisors
+            .insert(staff_id.clone(), to.clone())
+            .unwrap();
+        self.subordinates.get_mut(&from).unwrap().remove(&staff_id);
+        self.subordinates
+            .entry(to.clone())
+            .or_default()
+            .insert(staff_id.clone());
+        moves.push(Move { staff_id, from, to });
+    }
+}
@@ -0,0 +1,46 @@
+//! Module defines the [`SweepRule`] used by [`crate::Company::sweep_to`] and the
+//! [`SweepReport`] summarizing moved resources.
+
+/// Part of a staff member's available resources which is swept.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SweepAmount<R> {
+    /// The whole available balance.
+    All,
+    /// Everything above the given floor, the floor stays with the staff member.
+    AboveFloor(R),
+    /// The given percentage of the available balance.
+    Percentage(R),
+}
+
+/// Where swept resources are moved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepTarget {
+    /// To the staff member the sweep was started from.
+    Root,
+    /// To the direct supervisor of every swept staff member.
+    Supervisor,
+}
+
+/// Rule of sweeping resources up the hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepRule<R> {
+    pub amount: SweepAmount<R>,
+    pub target: SweepTarget,
+}
+
+/// Resources moved from a staff member during a sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sweep<ID, R> {
+    pub from: ID,
+    pub to: ID,
+    pub amount: R,
+}
+
+/// Summary of a sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepReport<ID, R> {
+    /// Moves made, supervisors go before their subordinates. Zero moves are omitted.
+    pub swept: Vec<Sweep<ID, R>>,
+    /// Total amount moved.
+    pub total: R,
+}
@@ -0,0 +1,150 @@
+//! Module defines [`TreeView`], a text tree of a company returned by [`Company::display_tree`]
+//! for logs and terminals.
+
+use crate::company::Company;
+use crate::resource::Resource;
+use crate::storage::{Backend, PersistentBackend, StaffSet};
+use crate::traits::StaffEntity;
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+
+/// Ordering of two staff members.
+type Compare<'a, V> = Box<dyn Fn(&V, &V) -> Ordering + 'a>;
+
+/// Text tree of a company rooted at the CEO, rendered with [`Display`]:
+///
+/// ```text
+/// CEO
+/// ├── Ann
+/// │   └── Eve
+/// └── Bob
+/// ```
+///
+/// Subordinates are ordered by ID unless another order is set with [`TreeView::sort_by_key`].
+pub struct TreeView<'a, V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    company: &'a Company<V, R, B>,
+    label: Box<dyn Fn(&V) -> String + 'a>,
+    order: Compare<'a, V>,
+    max_depth: Option<usize>,
+    balances: bool,
+}
+
+impl<V, R, B> Company<V, R, B>
+where
+    V: StaffEntity,
+    V::ID: Ord,
+    R: Resource + Display,
+    B: Backend<V::ID>,
+{
+    /// Returns the text tree of the company.
+    ///
+    /// # Arguments
+    /// - `label`: Formats the line of a staff member.
+    pub fn display_tree<'a>(&'a self, label: impl Fn(&V) -> String + 'a) -> TreeView<'a, V, R, B> {
+        TreeView {
+            company: self,
+            label: Box::new(label),
+            order: Box::new(|a: &V, b: &V| a.get_id().cmp(&b.get_id())),
+            max_depth: None,
+            balances: false,
+        }
+    }
+}
+
+impl<'a, V, R, B> TreeView<'a, V, R, B>
+where
+    V: StaffEntity,
+    R: Resource + Display,
+    B: Backend<V::ID>,
+{
+    /// Orders subordinates by the key returned by `key`, staff members with equal keys keep the
+    /// order by ID.
+    pub fn sort_by_key<K: Ord>(mut self, key: impl Fn(&V) -> K + 'a) -> Self {
+        let by_id = self.order;
+        self.order = Box::new(move |a, b| key(a).cmp(&key(b)).then_with(|| by_id(a, b)));
+        self
+    }
+
+    /// Shows only staff members at most `max_depth` levels below the CEO, `0` shows the CEO only.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Appends the balance in parentheses to every line.
+    pub fn with_balances(mut self) -> Self {
+        self.balances = true;
+        self
+    }
+
+    fn write_line(&self, f: &mut fmt::Formatter<'_>, staff: &V) -> fmt::Result {
+        write!(f, "{}", (self.label)(staff))?;
+        if self.balances {
+            let balance = self.company.resource(&staff.get_id()).unwrap();
+            write!(f, " ({balance})")?;
+        }
+        writeln!(f)
+    }
+
+    /// Returns the subordinates of a staff member in reverse order, so they are popped in order.
+    fn subordinates(&self, staff: &V) -> Vec<&'a V> {
+        let company = self.company;
+        let mut subordinates: Vec<&V> = company
+            .subordinate_set(&staff.get_id())
+            .into_iter()
+            .flat_map(StaffSet::iter)
+            .map(|id| company.get(id).unwrap())
+            .collect();
+        subordinates.sort_by(|a, b| (self.order)(b, a));
+        subordinates
+    }
+
+    /// Writes the subtree below the CEO depth first, keeping the subordinates left on every level
+    /// on a stack instead of recursing, so deep hierarchies do not overflow the call stack.
+    fn write_subordinates(&self, f: &mut fmt::Formatter<'_>, ceo: &V) -> fmt::Result {
+        if self.max_depth == Some(0) {
+            return Ok(());
+        }
+
+        let mut levels = vec![self.subordinates(ceo)];
+        let mut prefix = String::new();
+        while let Some(level) = levels.last_mut() {
+            let Some(subordinate) = level.pop() else {
+                levels.pop();
+                for _ in 0..4 {
+                    prefix.pop();
+                }
+                continue;
+            };
+
+            let last = level.is_empty();
+            write!(f, "{prefix}{}", if last { "└── " } else { "├── " })?;
+            self.write_line(f, subordinate)?;
+
+            if self
+                .max_depth
+                .is_some_and(|max_depth| levels.len() >= max_depth)
+            {
+                continue;
+            }
+            prefix.push_str(if last { "    " } else { "│   " });
+            levels.push(self.subordinates(subordinate));
+        }
+        Ok(())
+    }
+}
+
+impl<V, R, B> Display for TreeView<'_, V, R, B>
+where
+    V: StaffEntity,
+    R: Resource + Display,
+    B: Backend<V::ID>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(ceo) = self.company.ceo() else {
+            return Ok(());
+        };
+        self.write_line(f, ceo)?;
+        self.write_subordinates(f, ceo)
+    }
+}
@@ -0,0 +1,509 @@
+//! Module defines [`DurableCompany`], a [`Company`] whose mutations are appended to a write-ahead
+//! log on disk, and [`Company::open`] loading it back.
+//!
+//! A durable company is stored in a directory with two files:
+//! - `snapshot.json`: the compacted state of the company and the sequence number of the last
+//!   operation included in it.
+//! - `wal.jsonl`: one JSON line per [`Operation`] applied after the snapshot, each with its
+//!   sequence number.
+//!
+//! Opening a company loads the snapshot and replays the log. A torn last line left by a crash
+//! is dropped, records already covered by the snapshot are skipped, so a crash during
+//! compaction never applies an operation twice.
+//!
+//! Only staff, the hierarchy, balances, supply counters and the rounding mode are persisted.
+//! Limits, reservations, approvals, structural rules and the staff order are not, so
+//! [`DurableCompany::create`] rejects a company carrying any of them and a [`DurableCompany`]
+//! does not expose them.
+
+use crate::company::Company;
+use crate::errors::Error;
+use crate::operation::Operation;
+use crate::resource::{Resource, Rounding};
+use crate::storage::{Backend, PersistentBackend, StaffSet};
+use crate::traits::StaffEntity;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const LOG_FILE: &str = "wal.jsonl";
+
+/// Number of logged operations after which the log is compacted by default.
+pub const DEFAULT_COMPACTION_INTERVAL: u64 = 1000;
+
+/// Error of a [`DurableCompany`].
+#[derive(Debug)]
+pub enum WalError<ID, R> {
+    /// The operation was rejected by the company, nothing was logged.
+    Company(Error<ID, R>),
+
+    /// Reading or writing the files failed.
+    Io(io::Error),
+
+    /// A record in the middle of the log or the snapshot can not be decoded.
+    ///
+    /// # Parameters
+    /// * `file`: The damaged file.
+    /// * `line`: The number of the damaged line, starting from `1`.
+    Corrupted { file: PathBuf, line: usize },
+
+    /// A logged operation fails when replayed, so the log does not match the snapshot.
+    ///
+    /// # Parameters
+    /// * `seq`: The sequence number of the operation.
+    /// * `error`: The error of the operation.
+    Replay { seq: u64, error: Error<ID, R> },
+
+    /// The company given to [`DurableCompany::create`] carries reservations, pending requests,
+    /// limits, an approval policy, structural rules or a staff order, which are not persisted.
+    UnpersistedState,
+
+    /// A failed write could not be cut from the log, so the log no longer matches the company.
+    /// The handle rejects operations until [`DurableCompany::compact`] succeeds.
+    Damaged,
+}
+
+impl<ID, R> From<Error<ID, R>> for WalError<ID, R> {
+    fn from(error: Error<ID, R>) -> Self {
+        WalError::Company(error)
+    }
+}
+
+impl<ID, R> From<io::Error> for WalError<ID, R> {
+    fn from(error: io::Error) -> Self {
+        WalError::Io(error)
+    }
+}
+
+/// Staff member stored in a snapshot.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "V: Serialize, V::ID: Serialize, R: Serialize",
+    deserialize = "V: Deserialize<'de>, V::ID: Deserialize<'de>, R: Deserialize<'de>"
+))]
+struct StaffRecord<V: StaffEntity, R> {
+    staff: V,
+    supervisor_id: Option<V::ID>,
+    balance: R,
+}
+
+/// Content of `snapshot.json`.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "V: Serialize, V::ID: Serialize, R: Serialize",
+    deserialize = "V: Deserialize<'de>, V::ID: Deserialize<'de>, R: Deserialize<'de>"
+))]
+struct SnapshotRecord<V: StaffEntity, R> {
+    seq: u64,
+    rounding: Rounding,
+    minted: R,
+    burned: R,
+    dust: R,
+    /// Supervisors go before their subordinates, the CEO is first.
+    staff: Vec<StaffRecord<V, R>>,
+}
+
+/// Line of `wal.jsonl`.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "V: Serialize, V::ID: Serialize, R: Serialize",
+    deserialize = "V: Deserialize<'de>, V::ID: Deserialize<'de>, R: Deserialize<'de>"
+))]
+struct LogRecord<V: StaffEntity, R> {
+    seq: u64,
+    operation: Operation<V, R>,
+}
+
+/// [`Company`] persisted in a directory, see the [module documentation](self).
+pub struct DurableCompany<V: StaffEntity, R: Resource, B: Backend<V::ID> = PersistentBackend> {
+    company: Company<V, R, B>,
+    dir: PathBuf,
+    log: File,
+    seq: u64,
+    logged: u64,
+    compaction_interval: u64,
+    compaction_error: Option<io::Error>,
+    damaged: bool,
+}
+
+impl<V, R, B> Company<V, R, B>
+where
+    V: StaffEntity + Clone + Serialize + DeserializeOwned,
+    V::ID: Serialize + DeserializeOwned,
+    R: Resource + Serialize + DeserializeOwned,
+    B: Backend<V::ID>,
+{
+    /// Opens a company persisted in the directory `path` by [`DurableCompany::create`].
+    ///
+    /// # Errors
+    /// - [`WalError::Io`] if the files can not be read or the torn last record can not be cut.
+    /// - [`WalError::Corrupted`] if the snapshot or a record before the last one is damaged.
+    /// - [`WalError::Replay`] if a logged operation fails.
+    pub fn open(path: impl AsRef<Path>) -> Result<DurableCompany<V, R, B>, WalError<V::ID, R>> {
+        let dir = path.as_ref().to_path_buf();
+
+        let snapshot_path = dir.join(SNAPSHOT_FILE);
+        let snapshot: SnapshotRecord<V, R> = serde_json::from_slice(&fs::read(&snapshot_path)?)
+            .map_err(|_| WalError::Corrupted {
+                file: snapshot_path.clone(),
+                line: 1,
+            })?;
+        let mut seq = snapshot.seq;
+        let mut company = restore_snapshot(snapshot).ok_or(WalError::Corrupted {
+            file: snapshot_path,
+            line: 1,
+        })?;
+
+        let log_path = dir.join(LOG_FILE);
+        let mut log = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&log_path)?;
+
+        let mut reader = BufReader::new(&log);
+        let mut line = Vec::new();
+        let mut valid_len = 0;
+        let mut number = 0;
+        let mut logged = 0;
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
+            }
+            number += 1;
+
+            let record = serde_json::from_slice::<LogRecord<V, R>>(&line);
+            let complete = line.ends_with(b"\n");
+            match record {
+                Ok(record) if complete => {
+                    if record.seq > seq {
+                        company
+                            .apply(record.operation)
+                            .map_err(|error| WalError::Replay {
+                                seq: record.seq,
+                                error,
+                            })?;
+                        seq = record.seq;
+                        logged += 1;
+                    }
+                    valid_len += read as u64;
+                }
+                // only the last record can be torn by a crash
+                _ if reader.fill_buf()?.is_empty() => break,
+                _ => {
+                    return Err(WalError::Corrupted {
+                        file: log_path,
+                        line: number,
+                    })
+                }
+            }
+        }
+
+        drop(reader);
+        if valid_len < log.seek(SeekFrom::End(0))? {
+            log.set_len(valid_len)?;
+            log.sync_all()?;
+        }
+
+        Ok(DurableCompany {
+            company,
+            dir,
+            log,
+            seq,
+            logged,
+            compaction_interval: DEFAULT_COMPACTION_INTERVAL,
+            compaction_error: None,
+            damaged: false,
+        })
+    }
+}
+
+impl<V, R, B> DurableCompany<V, R, B>
+where
+    V: StaffEntity + Clone + Serialize + DeserializeOwned,
+    V::ID: Serialize + DeserializeOwned,
+    R: Resource + Serialize + DeserializeOwned,
+    B: Backend<V::ID>,
+{
+    /// Persists `company` in a new directory `path` and keeps logging its mutations.
+    ///
+    /// # Errors
+    /// - [`WalError::UnpersistedState`] if the company carries state which is not persisted, so
+    ///   it would differ after reopening.
+    /// - [`WalError::Io`] if the directory already holds a company or can not be written.
+    pub fn create(
+        path: impl AsRef<Path>,
+        company: Company<V, R, B>,
+    ) -> Result<Self, WalError<V::ID, R>> {
+        if company.has_unpersisted_state() {
+            return Err(WalError::UnpersistedState);
+        }
+
+        let dir = path.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        if dir.join(SNAPSHOT_FILE).exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "directory already holds a company",
+            )
+            .into());
+        }
+
+        let log = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(dir.join(LOG_FILE))?;
+        log.set_len(0)?;
+
+        let mut durable = Self {
+            company,
+            dir,
+            log,
+            seq: 0,
+            logged: 0,
+            compaction_interval: DEFAULT_COMPACTION_INTERVAL,
+            compaction_error: None,
+            damaged: false,
+        };
+        durable.compact()?;
+        Ok(durable)
+    }
+
+    /// Sets the number of logged operations after which the log is compacted automatically.
+    /// `0` disables automatic compaction.
+    pub fn with_compaction_interval(mut self, interval: u64) -> Self {
+        self.compaction_interval = interval;
+        self
+    }
+
+    /// Returns the company.
+    pub fn company(&self) -> &Company<V, R, B> {
+        &self.company
+    }
+
+    /// Consumes the handle returning the company, further mutations are not persisted.
+    pub fn into_company(self) -> Company<V, R, B> {
+        self.company
+    }
+
+    /// Returns the sequence number of the last logged operation.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Returns the error of the last automatic compaction, `None` if it succeeded.
+    ///
+    /// A failed compaction does not fail the operation which triggered it, it is retried after
+    /// every following operation.
+    pub fn compaction_error(&self) -> Option<&io::Error> {
+        self.compaction_error.as_ref()
+    }
+
+    /// Applies an operation and appends it to the log.
+    ///
+    /// The operation is logged only if it succeeds. If logging fails the company is restored
+    /// from a [`Company::snapshot`] and the written part of the record is cut from the log, so
+    /// the state in memory always matches the state on disk.
+    ///
+    /// The log is compacted once it holds the number of operations set by
+    /// [`DurableCompany::with_compaction_interval`], see [`DurableCompany::compaction_error`].
+    ///
+    /// # Errors
+    /// - [`WalError::Company`] if the operation fails.
+    /// - [`WalError::Io`] if the operation can not be logged.
+    /// - [`WalError::Damaged`] if an earlier record could not be cut from the log.
+    pub fn apply(&mut self, operation: Operation<V, R>) -> Result<(), WalError<V::ID, R>> {
+        if self.damaged {
+            return Err(WalError::Damaged);
+        }
+
+        let record = LogRecord {
+            seq: self.seq + 1,
+            operation,
+        };
+        let mut line = serde_json::to_vec(&record).map_err(io::Error::from)?;
+        line.push(b'\n');
+
+        let len = self.log.metadata()?.len();
+        let snapshot = self.company.snapshot();
+        self.company.apply(record.operation)?;
+        if let Err(error) = self.log.write_all(&line).and_then(|_| self.log.sync_data()) {
+            self.company.restore(snapshot);
+            if self
+                .log
+                .set_len(len)
+                .and_then(|_| self.log.sync_all())
+                .is_err()
+            {
+                self.damaged = true;
+            }
+            return Err(error.into());
+        }
+        self.seq += 1;
+        self.logged += 1;
+
+        if self.compaction_interval > 0 && self.logged >= self.compaction_interval {
+            self.compaction_error = self.write_snapshot().err();
+        }
+        Ok(())
+    }
+
+    /// See [`Company::hire`].
+    pub fn hire(&mut self, staff: V, supervisor_id: &V::ID) -> Result<V::ID, WalError<V::ID, R>> {
+        let staff_id = staff.get_id();
+        self.apply(Operation::Hire {
+            staff,
+            supervisor_id: supervisor_id.clone(),
+        })?;
+        Ok(staff_id)
+    }
+
+    /// See [`Company::fire`].
+    pub fn fire(&mut self, staff_id: &V::ID) -> Result<V, WalError<V::ID, R>> {
+        let staff = self.company.get(staff_id).cloned();
+        self.apply(Operation::Fire {
+            staff_id: staff_id.clone(),
+        })?;
+        Ok(staff.unwrap())
+    }
+
+    /// See [`Company::transfer`].
+    pub fn transfer(
+        &mut self,
+        from: &V::ID,
+        to: &V::ID,
+        amount: R,
+    ) -> Result<(), WalError<V::ID, R>> {
+        self.apply(Operation::Transfer {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        })
+    }
+
+    /// See [`Company::withdraw`].
+    pub fn withdraw(&mut self, staff_id: &V::ID, amount: R) -> Result<(), WalError<V::ID, R>> {
+        self.apply(Operation::Withdraw {
+            staff_id: staff_id.clone(),
+            amount,
+        })
+    }
+
+    /// See [`Company::mint`].
+    pub fn mint(&mut self, amount: R) -> Result<(), WalError<V::ID, R>> {
+        self.apply(Operation::Mint { amount })
+    }
+
+    /// See [`Company::change_supervisor`].
+    pub fn change_supervisor(
+        &mut self,
+        staff_id: &V::ID,
+        supervisor_id: &V::ID,
+    ) -> Result<(), WalError<V::ID, R>> {
+        self.apply(Operation::ChangeSupervisor {
+            staff_id: staff_id.clone(),
+            supervisor_id: supervisor_id.clone(),
+        })
+    }
+
+    /// Writes the current state to the snapshot and empties the log.
+    ///
+    /// The snapshot is written to a temporary file and renamed over the old one, so a crash
+    /// leaves either the old or the new snapshot in place.
+    ///
+    /// A successful compaction also repairs a [`WalError::Damaged`] log.
+    ///
+    /// # Errors
+    /// - [`WalError::Io`] if the files can not be written.
+    pub fn compact(&mut self) -> Result<(), WalError<V::ID, R>> {
+        self.write_snapshot()?;
+        Ok(())
+    }
+
+    fn write_snapshot(&mut self) -> io::Result<()> {
+        let snapshot = take_snapshot(&self.company, self.seq);
+        let temp_path = self.dir.join(format!("{SNAPSHOT_FILE}.tmp"));
+        let mut temp = File::create(&temp_path)?;
+        serde_json::to_writer(&mut temp, &snapshot).map_err(io::Error::from)?;
+        temp.sync_all()?;
+        fs::rename(&temp_path, self.dir.join(SNAPSHOT_FILE))?;
+        File::open(&self.dir)?.sync_all()?;
+
+        self.log.set_len(0)?;
+        self.log.sync_all()?;
+        self.logged = 0;
+        self.compaction_error = None;
+        self.damaged = false;
+        Ok(())
+    }
+}
+
+fn take_snapshot<V, R, B>(company: &Company<V, R, B>, seq: u64) -> SnapshotRecord<V, R>
+where
+    V: StaffEntity + Clone,
+    R: Resource,
+    B: Backend<V::ID>,
+{
+    let ceo_id = company.ceo().unwrap().get_id();
+    let mut order = vec![ceo_id];
+    let mut index = 0;
+    while index < order.len() {
+        if let Some(subordinates) = company.subordinate_set(&order[index]) {
+            order.extend(subordinates.iter().cloned());
+        }
+        index += 1;
+    }
+
+    let staff = order
+        .into_iter()
+        .map(|id| StaffRecord {
+            staff: company.get(&id).unwrap().clone(),
+            supervisor_id: company.supervisor(&id).cloned(),
+            balance: *company.resource(&id).unwrap(),
+        })
+        .collect();
+
+    SnapshotRecord {
+        seq,
+        rounding: company.rounding(),
+        minted: company.total_minted(),
+        burned: company.total_burned(),
+        dust: company.dust(),
+        staff,
+    }
+}
+
+/// Builds the company stored in a snapshot, `None` if the hierarchy is malformed.
+fn restore_snapshot<V, R, B>(snapshot: SnapshotRecord<V, R>) -> Option<Company<V, R, B>>
+where
+    V: StaffEntity,
+    R: Resource,
+    B: Backend<V::ID>,
+{
+    let mut records = snapshot.staff.into_iter();
+    let ceo = records.next()?;
+    if ceo.supervisor_id.is_some() {
+        return None;
+    }
+
+    let mut company = Company::new(ceo.staff).with_rounding(snapshot.rounding);
+    company.mint(ceo.balance);
+    for record in records {
+        let supervisor_id = record.supervisor_id?;
+        let staff_id = record.staff.get_id();
+        if company.get(&supervisor_id).is_none() || company.get(&staff_id).is_some() {
+            return None;
+        }
+        company.insert_staff(record.staff, &supervisor_id, record.balance);
+    }
+    company.set_supply(snapshot.minted, snapshot.burned, snapshot.dust);
+
+    company.check_supply().ok()?;
+    Some(company)
+}
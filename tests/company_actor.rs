@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::actor;
+    use staff_lib::errors::Error;
+    use staff_lib::storage::BTreeBackend;
+    use staff_lib::{Company, StaffEntity};
+    use std::cell::Cell;
+    use std::thread;
+
+    #[derive(Debug, PartialEq)]
+    struct Manager {
+        id: u32,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[derive(Debug)]
+    struct Counter {
+        id: u32,
+        visits: Cell<u32>,
+    }
+
+    impl StaffEntity for Counter {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_thread_actor() {
+        let (handle, thread) = actor::spawn(Company::<Manager, u64>::new(Manager { id: 0 }));
+        handle.mint(1000);
+        assert_eq!(handle.hire(Manager { id: 1 }, 0).unwrap(), 1);
+        assert_eq!(handle.hire(Manager { id: 2 }, 1).unwrap(), 2);
+
+        let workers: Vec<_> = (1..=2)
+            .map(|id| {
+                let handle = handle.clone();
+                thread::spawn(move || {
+                    for _ in 0..10 {
+                        handle.transfer(0, id, 1).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert_eq!(handle.resource(1), Some(100));
+        assert_eq!(handle.resource(2), Some(20));
+        assert_eq!(handle.supervisor(2), Some(1));
+        assert_eq!(handle.subordinates(1), Some(vec![2]));
+        assert!(matches!(handle.fire(0), Err(Error::CannotFireCeo)));
+        assert_eq!(handle.fire(1).unwrap(), Manager { id: 1 });
+        assert_eq!(handle.total_supply(), 1000);
+
+        drop(handle);
+        let company = thread.join().unwrap();
+        assert_eq!(company.get_all_staff().len(), 2);
+        assert_eq!(*company.resource(&0).unwrap(), 980);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_tokio_actor() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            let (handle, task) =
+                actor::spawn_tokio(Company::<Manager, u64>::new(Manager { id: 0 }));
+            handle.mint(100).await;
+            handle.hire(Manager { id: 1 }, 0).await.unwrap();
+            handle.transfer(1, 0, 5).await.unwrap();
+            assert_eq!(handle.resource(1).await, Some(5));
+            assert_eq!(handle.get_all_staff().await.len(), 2);
+
+            drop(handle);
+            let company = task.await.unwrap();
+            assert_eq!(*company.resource(&0).unwrap(), 95);
+        });
+    }
+
+    #[test]
+    fn test_actor_of_staff_which_is_not_sync() {
+        let mut company = Company::<Counter, u64, BTreeBackend>::new(Counter {
+            id: 0,
+            visits: Cell::new(0),
+        });
+        company.mint(100);
+        company.ceo().unwrap().visits.set(3);
+        company.reserve(&0, 40).unwrap();
+
+        let (handle, thread) = actor::spawn(company);
+        let visits = Cell::new(1);
+        assert_eq!(handle.hire(Counter { id: 1, visits }, 0).unwrap(), 1);
+        assert_eq!(handle.fire(1).unwrap().visits.get(), 1);
+        assert!(handle.withdraw(0, 70).is_err());
+
+        drop(handle);
+        let company = thread.join().unwrap();
+        assert_eq!(company.ceo().unwrap().visits.get(), 3);
+        assert_eq!(company.total_supply(), 100);
+        assert_eq!(company.available(&0), Some(60));
+        company.check_supply().unwrap();
+    }
+}
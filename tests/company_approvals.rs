@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::approval::{ApprovalPolicy, ApprovalStatus};
+    use staff_lib::{errors::Error, Company, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: Uuid,
+    }
+
+    impl Manager {
+        fn new() -> Self {
+            Self { id: Uuid::new_v4() }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_transfer_approval() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let worker_id = company.hire(Manager::new(), &manager_id).unwrap();
+        let other_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(1000);
+        company.transfer(&ceo_id, &worker_id, 500).unwrap();
+
+        company.set_approval_policy(Some(ApprovalPolicy {
+            threshold: 100,
+            required_approvals: 2,
+        }));
+
+        company.transfer(&worker_id, &other_id, 100).unwrap();
+        assert!(matches!(
+            company.transfer(&worker_id, &other_id, 101),
+            Err(Error::ApprovalRequired { .. })
+        ));
+
+        let request_id = company
+            .request_transfer(&worker_id, &other_id, 300)
+            .unwrap();
+        assert_eq!(company.available(&worker_id), Some(100));
+
+        if let Err(Error::StaffHasNoPermission { staff_id }) =
+            company.approve(&request_id, &other_id)
+        {
+            assert_eq!(staff_id, other_id);
+        } else {
+            panic!("Expected StaffHasNoPermission error");
+        }
+
+        assert_eq!(
+            company.approve(&request_id, &manager_id).unwrap(),
+            ApprovalStatus::Pending {
+                approvals: 1,
+                required: 2
+            }
+        );
+        assert_eq!(
+            company.approve(&request_id, &manager_id).unwrap(),
+            ApprovalStatus::Pending {
+                approvals: 1,
+                required: 2
+            }
+        );
+        assert_eq!(
+            company.approve(&request_id, &ceo_id).unwrap(),
+            ApprovalStatus::Executed
+        );
+
+        assert_eq!(*company.resource(&worker_id).unwrap(), 100);
+        assert_eq!(*company.resource(&other_id).unwrap(), 400);
+        assert!(company.transfer_request(&request_id).is_none());
+    }
+
+    #[test]
+    fn test_transfer_rejection() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(1000);
+
+        company.set_approval_policy(Some(ApprovalPolicy {
+            threshold: 10,
+            required_approvals: 3,
+        }));
+
+        // the CEO has no supervisors to approve its transfers
+        company.transfer(&ceo_id, &manager_id, 500).unwrap();
+
+        let request_id = company.request_transfer(&manager_id, &ceo_id, 200).unwrap();
+        let request = company.reject(&request_id, &ceo_id).unwrap();
+        assert_eq!(request.amount(), 200);
+        assert_eq!(company.available(&manager_id), Some(500));
+        assert!(matches!(
+            company.approve(&request_id, &ceo_id),
+            Err(Error::RequestNotFound { .. })
+        ));
+
+        let request_id = company.request_transfer(&manager_id, &ceo_id, 200).unwrap();
+        company.fire(&manager_id).unwrap();
+        assert!(company.transfer_request(&request_id).is_none());
+        assert_eq!(company.available(&ceo_id), Some(1000));
+    }
+
+    #[test]
+    fn test_transfer_request_without_approvers() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(1000);
+
+        company.set_approval_policy(Some(ApprovalPolicy {
+            threshold: 10,
+            required_approvals: 1,
+        }));
+
+        if let Err(Error::ApprovalNotRequired { staff_id }) =
+            company.request_transfer(&ceo_id, &manager_id, 500)
+        {
+            assert_eq!(staff_id, ceo_id);
+        } else {
+            panic!("Expected ApprovalNotRequired error");
+        }
+        assert_eq!(company.available(&ceo_id), Some(1000));
+
+        company.transfer(&ceo_id, &manager_id, 500).unwrap();
+        assert_eq!(company.available(&manager_id), Some(500));
+
+        company.set_approval_policy(None);
+        assert!(matches!(
+            company.request_transfer(&manager_id, &ceo_id, 100),
+            Err(Error::ApprovalNotRequired { .. })
+        ));
+        assert_eq!(company.available(&manager_id), Some(500));
+    }
+
+    #[test]
+    fn test_transfer_request_cancellation() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(1000);
+        company.transfer(&ceo_id, &manager_id, 500).unwrap();
+
+        company.set_approval_policy(Some(ApprovalPolicy {
+            threshold: 10,
+            required_approvals: 1,
+        }));
+
+        let request_id = company.request_transfer(&manager_id, &ceo_id, 200).unwrap();
+        assert_eq!(company.available(&manager_id), Some(300));
+
+        if let Err(Error::StaffHasNoPermission { staff_id }) =
+            company.cancel_request(&request_id, &ceo_id)
+        {
+            assert_eq!(staff_id, ceo_id);
+        } else {
+            panic!("Expected StaffHasNoPermission error");
+        }
+
+        let request = company.cancel_request(&request_id, &manager_id).unwrap();
+        assert_eq!(request.amount(), 200);
+        assert_eq!(company.available(&manager_id), Some(500));
+        assert!(company.transfer_request(&request_id).is_none());
+        assert!(matches!(
+            company.cancel_request(&request_id, &manager_id),
+            Err(Error::RequestNotFound { .. })
+        ));
+    }
+}
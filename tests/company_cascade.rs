@@ -0,0 +1,176 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::cascade::{CascadeRule, CascadeWeights};
+    use staff_lib::{errors::Error, Company, Rounding, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: Uuid,
+    }
+
+    impl Manager {
+        fn new() -> Self {
+            Self { id: Uuid::new_v4() }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_cascade_by_subtree_size() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let first_worker_id = company.hire(Manager::new(), &first_id).unwrap();
+        let second_worker_id = company.hire(Manager::new(), &first_id).unwrap();
+
+        let rule = CascadeRule {
+            keep_percentage: 10,
+            weights: CascadeWeights::SubtreeSize,
+        };
+        let report = company.mint_and_cascade(1000, &rule).unwrap();
+
+        assert_eq!(report.credited.len(), 5);
+        assert_eq!(report.dust, 0);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 100);
+        assert_eq!(*company.resource(&first_id).unwrap(), 67);
+        assert_eq!(*company.resource(&second_id).unwrap(), 225);
+        assert_eq!(*company.resource(&first_worker_id).unwrap(), 304);
+        assert_eq!(*company.resource(&second_worker_id).unwrap(), 304);
+        assert_eq!(company.total_minted(), 1000);
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_cascade_equal() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        for _ in 0..3 {
+            company.hire(Manager::new(), &ceo_id).unwrap();
+        }
+
+        let report = company
+            .mint_and_cascade(
+                100,
+                &CascadeRule {
+                    keep_percentage: 0,
+                    weights: CascadeWeights::Equal,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(report.dust, 1);
+        assert_eq!(company.dust(), 1);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 0);
+        for id in company.subordinates(&ceo_id).unwrap() {
+            assert_eq!(*company.resource(id).unwrap(), 33);
+        }
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_cascade_invalid_keep_percentage() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        company.hire(Manager::new(), &ceo_id).unwrap();
+
+        let rule = CascadeRule {
+            keep_percentage: 150,
+            weights: CascadeWeights::Equal,
+        };
+        assert!(matches!(
+            company.mint_and_cascade(1000, &rule),
+            Err(Error::InvalidPercentage { percentage: 150 })
+        ));
+        assert_eq!(company.total_minted(), 0);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 0);
+
+        let rule = CascadeRule {
+            keep_percentage: 100,
+            ..rule
+        };
+        company.mint_and_cascade(1000, &rule).unwrap();
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 1000);
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_cascade_order() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+        let rule = CascadeRule {
+            keep_percentage: 0,
+            weights: CascadeWeights::Equal,
+        };
+
+        // rounding up shortens the last subordinate, which is the last one to join
+        let mut company: Company<Manager, u64> = Company::new(ceo).with_rounding(Rounding::Ceil);
+        let ids: Vec<Uuid> = (0..3)
+            .map(|_| company.hire(Manager::new(), &ceo_id).unwrap())
+            .collect();
+        company.mint_and_cascade(100, &rule).unwrap();
+        let balances: Vec<u64> = ids
+            .iter()
+            .map(|id| *company.resource(id).unwrap())
+            .collect();
+        assert_eq!(balances, [34, 34, 32]);
+
+        // or the greatest ID if the company is sorted
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+        let mut company: Company<Manager, u64> = Company::new(ceo)
+            .with_rounding(Rounding::Ceil)
+            .with_sorted_order();
+        for _ in 0..3 {
+            company.hire(Manager::new(), &ceo_id).unwrap();
+        }
+        company.mint_and_cascade(100, &rule).unwrap();
+        let balances: Vec<u64> = company
+            .subordinates_in_order(&ceo_id)
+            .unwrap()
+            .iter()
+            .map(|id| *company.resource(id).unwrap())
+            .collect();
+        assert_eq!(balances, [34, 34, 32]);
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_cascade_does_not_overflow() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u32> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new(), &ceo_id).unwrap();
+
+        let report = company
+            .mint_and_cascade(
+                4_000_000_000,
+                &CascadeRule {
+                    keep_percentage: 50,
+                    weights: CascadeWeights::Equal,
+                },
+            )
+            .unwrap();
+        assert_eq!(report.dust, 0);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 2_000_000_000);
+        assert_eq!(*company.resource(&first_id).unwrap(), 1_000_000_000);
+        assert_eq!(*company.resource(&second_id).unwrap(), 1_000_000_000);
+        company.check_supply().unwrap();
+    }
+}
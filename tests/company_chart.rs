@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::errors::Error;
+    use staff_lib::{Company, StaffEntity};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: u32,
+        name: &'static str,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn company() -> Company<Manager, u64> {
+        let mut company = Company::new(Manager { id: 0, name: "CEO" });
+        company.mint(1000);
+        company.hire(Manager { id: 2, name: "Bob" }, &0).unwrap();
+        company
+            .hire(
+                Manager {
+                    id: 1,
+                    name: "Ann \"A\"",
+                },
+                &0,
+            )
+            .unwrap();
+        company.hire(Manager { id: 3, name: "Eve" }, &1).unwrap();
+        company
+    }
+
+    #[test]
+    fn test_chart_dot() {
+        let company = company();
+
+        let dot = company.chart(|manager| manager.name.to_string()).to_dot();
+        assert_eq!(
+            dot,
+            "digraph company {\n\
+             \x20   \"0\" [label=\"CEO\"];\n\
+             \x20   \"1\" [label=\"Ann \\\"A\\\"\"];\n\
+             \x20   \"3\" [label=\"Eve\"];\n\
+             \x20   \"2\" [label=\"Bob\"];\n\
+             \x20   \"0\" -> \"1\";\n\
+             \x20   \"1\" -> \"3\";\n\
+             \x20   \"0\" -> \"2\";\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_chart_mermaid_subtree_with_balances() {
+        let company = company();
+
+        let mermaid = company
+            .chart(|manager| manager.name.to_string())
+            .subtree(&1)
+            .unwrap()
+            .with_balances()
+            .to_mermaid();
+        assert_eq!(
+            mermaid,
+            "graph TD\n\
+             \x20   n0[\"Ann #quot;A#quot;<br/>81\"]\n\
+             \x20   n1[\"Eve<br/>9\"]\n\
+             \x20   n0 --> n1\n"
+        );
+    }
+
+    #[test]
+    fn test_chart_follows_change_supervisor() {
+        let mut company = company();
+        company.change_supervisor(&3, &2).unwrap();
+
+        let mermaid = company.chart(|manager| manager.id.to_string()).to_mermaid();
+        assert!(mermaid.contains("n0[\"0\"]\n    n1[\"1\"]\n    n2[\"2\"]\n    n3[\"3\"]\n"));
+        assert!(mermaid.ends_with("n0 --> n1\n    n0 --> n2\n    n2 --> n3\n"));
+
+        assert!(matches!(
+            company.chart(|manager| manager.id.to_string()).subtree(&7),
+            Err(Error::StaffNotFound { staff_id: 7 })
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::csv::CsvError;
+    use staff_lib::{Company, Fixed, StaffEntity};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: u32,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn import(csv: &str) -> Result<Company<Manager, u64>, CsvError<u32>> {
+        Company::from_csv(csv.as_bytes(), |&id| Manager { id })
+    }
+
+    #[test]
+    fn test_csv_import() {
+        let company =
+            import("id,supervisor_id,balance\n3, 1, 7\n1,0,20\n\n0,,100\n2,0,0\n").unwrap();
+
+        assert_eq!(company.ceo(), Some(&Manager { id: 0 }));
+        assert_eq!(company.supervisor(&1), Some(&0));
+        assert_eq!(company.supervisor(&2), Some(&0));
+        assert_eq!(company.supervisor(&3), Some(&1));
+        assert_eq!(*company.resource(&3).unwrap(), 7);
+        assert_eq!(company.total_supply(), 127);
+        assert_eq!(company.total_minted(), 127);
+        assert!(company.check_supply().is_ok());
+    }
+
+    #[test]
+    fn test_csv_export_roundtrip() {
+        let mut company: Company<Manager, Fixed<2>> = Company::new(Manager { id: 0 });
+        company.mint("100".parse().unwrap());
+        company.hire(Manager { id: 2 }, &0).unwrap();
+        company.hire(Manager { id: 1 }, &0).unwrap();
+        company.hire(Manager { id: 3 }, &1).unwrap();
+
+        let mut out = Vec::new();
+        company.to_csv(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text,
+            "id,supervisor_id,balance\n0,,81.00\n1,0,8.10\n2,0,10.00\n3,1,0.90\n"
+        );
+
+        let imported: Company<Manager, Fixed<2>> =
+            Company::from_csv(text.as_bytes(), |&id| Manager { id }).unwrap();
+        assert!(company.diff(&imported).is_empty());
+    }
+
+    #[test]
+    fn test_csv_hierarchy_errors() {
+        assert!(matches!(
+            import("0,,1\n1,0,1\n1,0,2\n"),
+            Err(CsvError::DuplicateId {
+                line: 3,
+                first_line: 2,
+                staff_id: 1
+            })
+        ));
+        assert!(matches!(
+            import("1,2,1\n2,1,1\n"),
+            Err(CsvError::MissingCeo)
+        ));
+        assert!(matches!(
+            import("0,,1\n1,0,1\n2,,1\n"),
+            Err(CsvError::MultipleCeos { lines }) if lines == vec![1, 3]
+        ));
+        assert!(matches!(
+            import("0,,1\n1,5,1\n"),
+            Err(CsvError::Orphan {
+                line: 2,
+                staff_id: 1,
+                supervisor_id: 5
+            })
+        ));
+        assert!(matches!(
+            import("0,,1\n4,3,1\n1,2,1\n2,3,1\n3,1,1\n"),
+            Err(CsvError::Cycle { lines }) if lines == vec![5, 3, 4]
+        ));
+    }
+
+    #[test]
+    fn test_csv_malformed_rows() {
+        assert!(matches!(
+            import("0,,1\n1,0\n"),
+            Err(CsvError::Malformed { line: 2, .. })
+        ));
+        assert!(matches!(
+            import("0,,1\nx,0,1\n"),
+            Err(CsvError::Malformed { line: 2, .. })
+        ));
+        assert!(matches!(
+            import("id,supervisor_id,balance\n0,,1\n1,0,-1\n"),
+            Err(CsvError::Malformed { line: 3, .. })
+        ));
+        assert!(matches!(
+            Company::<Manager, u64>::from_csv("0,,1\n1,0,1\n".as_bytes(), |_| Manager { id: 0 }),
+            Err(CsvError::IdMismatch {
+                line: 2,
+                staff_id: 1
+            })
+        ));
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::diff::{BalanceChange, SupervisorChange};
+    use staff_lib::{Company, StaffEntity};
+
+    #[derive(Debug, Clone)]
+    struct Manager {
+        id: u32,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_diff_of_reorganization() {
+        let mut company: Company<Manager, u64> = Company::new(Manager { id: 0 });
+        company.mint(1000);
+        company.hire(Manager { id: 1 }, &0).unwrap();
+        company.hire(Manager { id: 2 }, &0).unwrap();
+        company.hire(Manager { id: 3 }, &1).unwrap();
+
+        let before = company.snapshot();
+        assert!(before.company().diff(&company).is_empty());
+
+        company.fire(&1).unwrap();
+        company.change_supervisor(&3, &2).unwrap();
+        company.hire(Manager { id: 4 }, &2).unwrap();
+
+        let diff = before.company().diff(&company);
+        assert_eq!(diff.added, vec![4]);
+        assert_eq!(diff.removed, vec![1]);
+        assert_eq!(
+            diff.supervisor_changes,
+            vec![SupervisorChange {
+                staff_id: 3,
+                from: 1,
+                to: 2,
+            }]
+        );
+        assert_eq!(
+            diff.balance_changes,
+            vec![
+                BalanceChange {
+                    staff_id: 0,
+                    before: 810,
+                    after: 900,
+                },
+                BalanceChange {
+                    staff_id: 1,
+                    before: 90,
+                    after: 0,
+                },
+                BalanceChange {
+                    staff_id: 2,
+                    before: 90,
+                    after: 81,
+                },
+                BalanceChange {
+                    staff_id: 4,
+                    before: 0,
+                    after: 9,
+                },
+            ]
+        );
+
+        assert_eq!(
+            diff.to_string(),
+            "+ 4\n- 1\n~ 3: supervisor 1 -> 2\n$ 0: 810 -> 900\n$ 1: 90 -> 0\n$ 2: 90 -> 81\n$ 4: 0 -> 9\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_is_reversible() {
+        let mut company: Company<Manager, u64> = Company::new(Manager { id: 0 });
+        company.mint(100);
+        company.hire(Manager { id: 1 }, &0).unwrap();
+        let before = company.clone();
+        company.fire(&1).unwrap();
+
+        let forward = before.diff(&company);
+        let backward = company.diff(&before);
+        assert_eq!(forward.added, backward.removed);
+        assert_eq!(forward.removed, backward.added);
+        assert_eq!(company.diff(&company).to_string(), "no changes\n");
+    }
+}
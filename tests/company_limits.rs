@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::approval::ApprovalPolicy;
+    use staff_lib::limits::{LimitKind, Limits};
+    use staff_lib::{errors::Error, Company, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: Uuid,
+    }
+
+    impl Manager {
+        fn new() -> Self {
+            Self { id: Uuid::new_v4() }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_transfer_limits() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(1000);
+
+        let limits = Limits {
+            max_transfer: Some(100),
+            period_spending: Some(150),
+            ..Limits::default()
+        };
+        company.set_limits(&ceo_id, limits).unwrap();
+
+        if let Err(Error::LimitExceeded {
+            staff_id,
+            kind,
+            limit,
+            attempted,
+        }) = company.transfer(&ceo_id, &manager_id, 101)
+        {
+            assert_eq!(staff_id, ceo_id);
+            assert_eq!(kind, LimitKind::MaxTransfer);
+            assert_eq!(limit, 100);
+            assert_eq!(attempted, 101);
+        } else {
+            panic!("Expected LimitExceeded error");
+        }
+
+        company.transfer(&ceo_id, &manager_id, 100).unwrap();
+        assert!(matches!(
+            company.withdraw(&ceo_id, 60),
+            Err(Error::LimitExceeded {
+                kind: LimitKind::PeriodSpending,
+                attempted: 160,
+                ..
+            })
+        ));
+        company.withdraw(&ceo_id, 50).unwrap();
+        assert_eq!(company.spent(&ceo_id), 150);
+
+        company.advance_period();
+        assert_eq!(company.spent(&ceo_id), 0);
+        company.transfer(&ceo_id, &manager_id, 100).unwrap();
+
+        company
+            .set_limits(
+                &manager_id,
+                Limits {
+                    max_balance: Some(200),
+                    ..Limits::default()
+                },
+            )
+            .unwrap();
+        assert!(matches!(
+            company.transfer(&ceo_id, &manager_id, 1),
+            Err(Error::LimitExceeded {
+                kind: LimitKind::MaxBalance,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_inherited_limits() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let worker_id = company.hire(Manager::new(), &manager_id).unwrap();
+        company.mint(1000);
+        company.transfer(&ceo_id, &worker_id, 500).unwrap();
+
+        let limits = Limits {
+            max_transfer: Some(10),
+            ..Limits::default()
+        };
+        company.set_limits(&manager_id, limits).unwrap();
+        assert!(company.effective_limits(&worker_id).is_none());
+        company.transfer(&worker_id, &ceo_id, 20).unwrap();
+
+        company
+            .set_limits(
+                &manager_id,
+                Limits {
+                    inherit: true,
+                    ..limits
+                },
+            )
+            .unwrap();
+        assert!(company.transfer(&worker_id, &ceo_id, 20).is_err());
+
+        // firing is not a spending and is not limited
+        company.fire(&worker_id).unwrap();
+        assert_eq!(*company.resource(&manager_id).unwrap(), 480);
+    }
+
+    #[test]
+    fn test_distribute_limits() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(1000);
+
+        let limits = Limits {
+            max_transfer: Some(10),
+            ..Limits::default()
+        };
+        company.set_limits(&ceo_id, limits).unwrap();
+        assert!(matches!(
+            company.distribute(&ceo_id, &[(first_id, 1)], 500),
+            Err(Error::LimitExceeded {
+                kind: LimitKind::MaxTransfer,
+                attempted: 500,
+                ..
+            })
+        ));
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 1000);
+
+        company.distribute(&ceo_id, &[(first_id, 1)], 10).unwrap();
+        assert_eq!(company.spent(&ceo_id), 10);
+        company.remove_limits(&ceo_id);
+
+        company
+            .set_limits(
+                &second_id,
+                Limits {
+                    max_balance: Some(50),
+                    ..Limits::default()
+                },
+            )
+            .unwrap();
+        let recipients = [(first_id, 1), (second_id, 1), (second_id, 1)];
+        assert!(matches!(
+            company.distribute(&ceo_id, &recipients, 90),
+            Err(Error::LimitExceeded {
+                kind: LimitKind::MaxBalance,
+                attempted: 60,
+                ..
+            })
+        ));
+
+        company.set_approval_policy(Some(ApprovalPolicy {
+            threshold: 100,
+            required_approvals: 1,
+        }));
+        assert!(matches!(
+            company.distribute(&first_id, &[(ceo_id, 1)], 101),
+            Err(Error::ApprovalRequired { amount: 101, .. })
+        ));
+        assert_eq!(*company.resource(&first_id).unwrap(), 10);
+    }
+}
@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use staff_lib::operation::Operation;
+    use staff_lib::{Company, StaffEntity};
+    use std::collections::BTreeMap;
+
+    const CEO_ID: u8 = 0;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: u8,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u8;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    /// Reference model: every staff member with its supervisor and balance.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Model {
+        staff: BTreeMap<u8, (Option<u8>, u64)>,
+        supply: u64,
+    }
+
+    impl Model {
+        fn new() -> Self {
+            Self {
+                staff: BTreeMap::from([(CEO_ID, (None, 0))]),
+                supply: 0,
+            }
+        }
+
+        fn balance(&mut self, id: u8) -> &mut u64 {
+            &mut self.staff.get_mut(&id).unwrap().1
+        }
+
+        fn is_ancestor(&self, ancestor: u8, mut id: u8) -> bool {
+            while let Some((Some(supervisor), _)) = self.staff.get(&id) {
+                if *supervisor == ancestor {
+                    return true;
+                }
+                id = *supervisor;
+            }
+            false
+        }
+
+        fn apply(&mut self, operation: &Operation<Manager, u64>) -> bool {
+            match operation {
+                Operation::Hire {
+                    staff,
+                    supervisor_id,
+                } => {
+                    if !self.staff.contains_key(supervisor_id) || self.staff.contains_key(&staff.id)
+                    {
+                        return false;
+                    }
+                    let share = *self.balance(*supervisor_id) / 10;
+                    *self.balance(*supervisor_id) -= share;
+                    self.staff.insert(staff.id, (Some(*supervisor_id), share));
+                }
+                Operation::Fire { staff_id } => {
+                    let Some((Some(supervisor_id), balance)) = self.staff.get(staff_id).cloned()
+                    else {
+                        return false;
+                    };
+                    self.staff.remove(staff_id);
+                    *self.balance(supervisor_id) += balance;
+                    for (supervisor, _) in self.staff.values_mut() {
+                        if *supervisor == Some(*staff_id) {
+                            *supervisor = Some(supervisor_id);
+                        }
+                    }
+                }
+                Operation::Transfer { from, to, amount } => {
+                    if !self.staff.contains_key(to)
+                        || self.staff.get(from).is_none_or(|s| s.1 < *amount)
+                    {
+                        return false;
+                    }
+                    *self.balance(*from) -= amount;
+                    *self.balance(*to) += amount;
+                }
+                Operation::Withdraw { staff_id, amount } => {
+                    if self.staff.get(staff_id).is_none_or(|s| s.1 < *amount) {
+                        return false;
+                    }
+                    *self.balance(*staff_id) -= amount;
+                    self.supply -= amount;
+                }
+                Operation::Mint { amount } => {
+                    *self.balance(CEO_ID) += amount;
+                    self.supply += amount;
+                }
+                Operation::ChangeSupervisor {
+                    staff_id,
+                    supervisor_id,
+                } => {
+                    if staff_id == supervisor_id {
+                        return true;
+                    }
+                    if !self.staff.contains_key(supervisor_id)
+                        || *staff_id == CEO_ID
+                        || !self.staff.contains_key(staff_id)
+                        || self.is_ancestor(*staff_id, *supervisor_id)
+                    {
+                        return false;
+                    }
+                    self.staff.get_mut(staff_id).unwrap().0 = Some(*supervisor_id);
+                }
+            }
+            true
+        }
+    }
+
+    fn operation() -> impl Strategy<Value = Operation<Manager, u64>> {
+        let id = || 0u8..24;
+        prop_oneof![
+            4 => (id(), id()).prop_map(|(id, supervisor_id)| Operation::Hire {
+                staff: Manager { id },
+                supervisor_id,
+            }),
+            2 => id().prop_map(|staff_id| Operation::Fire { staff_id }),
+            4 => (id(), id(), 0u64..500).prop_map(|(from, to, amount)| Operation::Transfer {
+                from,
+                to,
+                amount,
+            }),
+            1 => (id(), 0u64..500).prop_map(|(staff_id, amount)| Operation::Withdraw {
+                staff_id,
+                amount,
+            }),
+            1 => (0u64..10_000).prop_map(|amount| Operation::Mint { amount }),
+            2 => (id(), id()).prop_map(|(staff_id, supervisor_id)| {
+                Operation::ChangeSupervisor {
+                    staff_id,
+                    supervisor_id,
+                }
+            }),
+        ]
+    }
+
+    fn check_invariants(company: &Company<Manager, u64>, model: &Model) {
+        company.check_supply().unwrap();
+        assert_eq!(company.total_supply(), model.supply);
+
+        let mut ids = company.get_all_staff();
+        ids.sort();
+        assert_eq!(ids, model.staff.keys().cloned().collect::<Vec<_>>());
+
+        for id in ids {
+            let (supervisor, balance) = model.staff[&id];
+            assert_eq!(company.supervisor(&id).cloned(), supervisor);
+            assert_eq!(*company.resource(&id).unwrap(), balance);
+
+            // every subordinate points back to its supervisor
+            for subordinate in company.subordinates(&id).into_iter().flatten() {
+                assert_eq!(company.supervisor(subordinate), Some(&id));
+            }
+            if let Some(supervisor) = supervisor {
+                assert!(company.subordinates(&supervisor).unwrap().contains(&id));
+            }
+
+            // the chain of supervisors reaches the CEO without cycles
+            let mut current = id;
+            let mut steps = 0;
+            while let Some(supervisor) = company.supervisor(&current) {
+                current = *supervisor;
+                steps += 1;
+                assert!(steps <= model.staff.len());
+            }
+            assert_eq!(current, CEO_ID);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_company_matches_model(operations in prop::collection::vec(operation(), 1..200)) {
+            let mut company: Company<Manager, u64> = Company::new(Manager { id: CEO_ID });
+            let mut model = Model::new();
+
+            for operation in operations {
+                let expected = model.apply(&operation);
+                let result = company.apply(operation.clone());
+                prop_assert_eq!(result.is_ok(), expected, "{:?}: {:?}", operation, result);
+                check_invariants(&company, &model);
+            }
+        }
+    }
+}
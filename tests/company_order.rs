@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::order::StaffOrder;
+    use staff_lib::{Company, StaffEntity};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: u32,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn populate(company: &mut Company<Manager, u64>) {
+        for id in [7, 3, 9, 1] {
+            company.hire(Manager { id }, &5).unwrap();
+        }
+        for id in [8, 2, 6] {
+            company.hire(Manager { id }, &3).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_insertion_order() {
+        let mut company = Company::new(Manager { id: 5 }).with_insertion_order();
+        populate(&mut company);
+        assert_eq!(company.staff_order(), StaffOrder::Insertion);
+
+        assert_eq!(company.get_all_staff(), vec![5, 7, 3, 9, 1, 8, 2, 6]);
+        assert_eq!(company.subordinates_in_order(&5), Some(vec![7, 3, 9, 1]));
+
+        company.fire(&3).unwrap();
+        company.hire(Manager { id: 3 }, &9).unwrap();
+        assert_eq!(company.get_all_staff(), vec![5, 7, 9, 1, 8, 2, 6, 3]);
+        assert_eq!(
+            company.subordinates_in_order(&5),
+            Some(vec![7, 9, 1, 8, 2, 6])
+        );
+        assert_eq!(company.clone().get_all_staff(), company.get_all_staff());
+    }
+
+    #[test]
+    fn test_sorted_order() {
+        let mut company = Company::new(Manager { id: 5 }).with_sorted_order();
+        populate(&mut company);
+        assert_eq!(company.staff_order(), StaffOrder::Sorted);
+
+        assert_eq!(company.get_all_staff(), vec![1, 2, 3, 5, 6, 7, 8, 9]);
+        assert_eq!(company.subordinates_in_order(&3), Some(vec![2, 6, 8]));
+        assert_eq!(company.subordinates_in_order(&9), None);
+    }
+
+    #[test]
+    fn test_unordered_by_default() {
+        let mut company = Company::new(Manager { id: 5 });
+        populate(&mut company);
+        assert_eq!(company.staff_order(), StaffOrder::Unordered);
+
+        let mut ids = company.get_all_staff();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3, 5, 6, 7, 8, 9]);
+        assert_eq!(company.subordinates_in_order(&3).unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::payroll::PayrollRule;
+    use staff_lib::{errors::Error, Company, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: Uuid,
+        weight: u64,
+    }
+
+    impl Manager {
+        fn new(weight: u64) -> Self {
+            Self {
+                id: Uuid::new_v4(),
+                weight,
+            }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_fixed_salary_payroll() {
+        let ceo = Manager::new(1);
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(1), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new(1), &ceo_id).unwrap();
+        let worker_id = company.hire(Manager::new(1), &first_id).unwrap();
+        company.mint(1000);
+
+        let report = company.run_payroll(&PayrollRule::FixedSalary(100)).unwrap();
+        assert_eq!(report.payouts.len(), 3);
+        assert!(report.shortfalls.is_empty());
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 800);
+        assert_eq!(*company.resource(&first_id).unwrap(), 0);
+        assert_eq!(*company.resource(&second_id).unwrap(), 100);
+        assert_eq!(*company.resource(&worker_id).unwrap(), 100);
+
+        let report = company.run_payroll(&PayrollRule::FixedSalary(900)).unwrap();
+        assert!(report.payouts.is_empty());
+        assert_eq!(report.shortfalls.len(), 3);
+        assert_eq!(report.shortfalls[0].from, ceo_id);
+        assert_eq!(report.shortfalls[0].available, 800);
+        assert_eq!(report.shortfalls[2].from, first_id);
+        assert_eq!(report.shortfalls[2].to, worker_id);
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_weighted_payroll() {
+        let ceo = Manager::new(0);
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(1), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new(2), &ceo_id).unwrap();
+        company.mint(1000);
+
+        let weight = |manager: &Manager| manager.weight;
+        let report = company
+            .run_payroll(&PayrollRule::Weighted {
+                percentage: 10,
+                weight: &weight,
+            })
+            .unwrap();
+
+        assert_eq!(report.payouts.len(), 2);
+        assert_eq!(report.dust, 1);
+        assert_eq!(*company.resource(&first_id).unwrap(), 33);
+        assert_eq!(*company.resource(&second_id).unwrap(), 66);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 900);
+
+        company.run_payroll(&PayrollRule::Percentage(50)).unwrap();
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 450);
+        assert_eq!(*company.resource(&first_id).unwrap(), 33 + 225);
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_payroll_invalid_percentage() {
+        let ceo = Manager::new(0);
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(0), &ceo_id).unwrap();
+        company.mint(1000);
+
+        let weight = |manager: &Manager| manager.weight;
+        assert!(matches!(
+            company.run_payroll(&PayrollRule::Weighted {
+                percentage: 200,
+                weight: &weight,
+            }),
+            Err(Error::InvalidPercentage { percentage: 200 })
+        ));
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 1000);
+
+        let report = company
+            .run_payroll(&PayrollRule::Weighted {
+                percentage: 100,
+                weight: &weight,
+            })
+            .unwrap();
+        assert_eq!(report.dust, 1000);
+        assert_eq!(*company.resource(&first_id).unwrap(), 0);
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_payroll_shortfall_order() {
+        let ceo = Manager::new(0);
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let ids: Vec<Uuid> = (0..10)
+            .map(|_| company.hire(Manager::new(0), &ceo_id).unwrap())
+            .collect();
+        company.mint(30);
+
+        // reports are paid in the order they joined, so the same ones are paid on every run
+        let report = company.run_payroll(&PayrollRule::FixedSalary(10)).unwrap();
+        let paid: Vec<Uuid> = report.payouts.iter().map(|payout| payout.to).collect();
+        assert_eq!(paid, ids[..3]);
+        let skipped: Vec<Uuid> = report.shortfalls.iter().map(|s| s.to).collect();
+        assert_eq!(skipped, ids[3..]);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 0);
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_payroll_percentage_does_not_overflow() {
+        let ceo = Manager::new(0);
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u32> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(0), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new(0), &ceo_id).unwrap();
+        company.mint(4_000_000_000);
+
+        let report = company.run_payroll(&PayrollRule::Percentage(50)).unwrap();
+        assert_eq!(report.dust, 0);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 2_000_000_000);
+        assert_eq!(*company.resource(&first_id).unwrap(), 1_000_000_000);
+        assert_eq!(*company.resource(&second_id).unwrap(), 1_000_000_000);
+        company.check_supply().unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::errors::Error;
+    use staff_lib::operation::Operation;
+    use staff_lib::{Company, StaffEntity};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: u32,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn company() -> Company<Manager, u64> {
+        let mut company = Company::new(Manager { id: 0 });
+        company.mint(1000);
+        company.hire(Manager { id: 1 }, &0).unwrap();
+        company.hire(Manager { id: 2 }, &1).unwrap();
+        company
+    }
+
+    #[test]
+    fn test_plan_leaves_company_untouched() {
+        let company = company();
+
+        let plan = company.plan([
+            Operation::Fire { staff_id: 1 },
+            Operation::Transfer {
+                from: 2,
+                to: 0,
+                amount: 5,
+            },
+        ]);
+
+        assert!(plan.is_ok());
+        assert_eq!(plan.balances(), vec![(0, 995), (2, 5)]);
+        assert_eq!(plan.diff().removed, vec![1]);
+        assert_eq!(plan.steps()[0].diff.supervisor_changes.len(), 1);
+        assert_eq!(plan.steps()[1].diff.balance_changes.len(), 2);
+
+        assert_eq!(company.get_all_staff().len(), 3);
+        assert_eq!(*company.resource(&0).unwrap(), 900);
+        assert_eq!(company.supervisor(&2), Some(&1));
+    }
+
+    #[test]
+    fn test_plan_reports_errors() {
+        let company = company();
+
+        let plan = company.plan([
+            Operation::Fire { staff_id: 0 },
+            Operation::Transfer {
+                from: 2,
+                to: 1,
+                amount: 100,
+            },
+            Operation::Hire {
+                staff: Manager { id: 3 },
+                supervisor_id: 2,
+            },
+        ]);
+
+        assert!(!plan.is_ok());
+        let errors: Vec<usize> = plan.errors().map(|(index, _)| index).collect();
+        assert_eq!(errors, vec![0, 1]);
+        assert!(matches!(plan.steps()[0].result, Err(Error::CannotFireCeo)));
+        assert!(matches!(
+            plan.steps()[1].result,
+            Err(Error::InsufficientResourcesError { staff_id: 2, .. })
+        ));
+        assert!(plan.steps()[1].diff.is_empty());
+        assert_eq!(plan.diff().added, vec![3]);
+
+        let planned = plan.into_company();
+        assert_eq!(planned.supervisor(&3), Some(&2));
+        assert!(company.get(&3).is_none());
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::{errors::Error, Company, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: Uuid,
+    }
+
+    impl Manager {
+        fn new() -> Self {
+            Self { id: Uuid::new_v4() }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_reserve_and_commit() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(1000);
+
+        let reservation_id = company.reserve(&ceo_id, 700).unwrap();
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 1000);
+        assert_eq!(company.reserved(&ceo_id), 700);
+        assert_eq!(company.available(&ceo_id), Some(300));
+
+        if let Err(Error::InsufficientResourcesError {
+            staff_id,
+            required,
+            available,
+            reserved,
+        }) = company.transfer(&ceo_id, &manager_id, 500)
+        {
+            assert_eq!(staff_id, ceo_id);
+            assert_eq!(required, 500);
+            assert_eq!(available, 300);
+            assert_eq!(reserved, 700);
+        } else {
+            panic!("Expected InsufficientResourcesError error");
+        }
+        assert!(company.withdraw(&ceo_id, 301).is_err());
+
+        company.commit(&reservation_id, &manager_id).unwrap();
+        assert_eq!(*company.resource(&manager_id).unwrap(), 700);
+        assert_eq!(company.available(&ceo_id), Some(300));
+        assert!(matches!(
+            company.commit(&reservation_id, &manager_id),
+            Err(Error::ReservationNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_release_and_fire() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(100);
+        company.transfer(&ceo_id, &manager_id, 100).unwrap();
+
+        let first = company.reserve(&manager_id, 40).unwrap();
+        let second = company.reserve(&manager_id, 60).unwrap();
+        assert!(company.reserve(&manager_id, 1).is_err());
+
+        let released = company.release(&first).unwrap();
+        assert_eq!(released.amount(), 40);
+        assert_eq!(company.available(&manager_id), Some(40));
+
+        company.fire(&manager_id).unwrap();
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 100);
+        assert_eq!(company.reserved(&ceo_id), 60);
+        assert_eq!(company.reservation(&second).unwrap().staff_id(), &ceo_id);
+
+        company.release(&second).unwrap();
+        assert_eq!(company.available(&ceo_id), Some(100));
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::{Company, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: Uuid,
+        name: String,
+    }
+
+    impl Manager {
+        fn new(name: &str) -> Self {
+            Self {
+                id: Uuid::new_v4(),
+                name: name.to_string(),
+            }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_restore_snapshot() {
+        let ceo = Manager::new("ceo");
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        company.mint(1000);
+        let first_id = company.hire(Manager::new("first"), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new("second"), &first_id).unwrap();
+
+        let snapshot = company.snapshot();
+
+        company.transfer(&ceo_id, &second_id, 300).unwrap();
+        let fired = company.fire(&first_id).unwrap();
+        assert_eq!(fired.name, "first");
+        company.hire(Manager::new("third"), &ceo_id).unwrap();
+        company.withdraw(&ceo_id, 100).unwrap();
+
+        assert_eq!(*snapshot.company().resource(&second_id).unwrap(), 10);
+        assert_eq!(snapshot.company().get(&first_id), Some(&fired));
+
+        company.restore(snapshot);
+        assert_eq!(company.get_all_staff().len(), 3);
+        assert_eq!(company.supervisor(&second_id), Some(&first_id));
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 900);
+        assert_eq!(*company.resource(&first_id).unwrap(), 90);
+        assert_eq!(*company.resource(&second_id).unwrap(), 10);
+        assert_eq!(company.total_supply(), 1000);
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let ceo = Manager::new("ceo");
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        company.mint(100);
+        let id = company.hire(Manager::new("first"), &ceo_id).unwrap();
+
+        let mut copy = company.clone();
+        copy.get_mut(&id).unwrap().name = "renamed".to_string();
+        copy.transfer(&ceo_id, &id, 50).unwrap();
+
+        assert_eq!(company.get(&id).unwrap().name, "first");
+        assert_eq!(*company.resource(&id).unwrap(), 10);
+        assert_eq!(copy.get(&id).unwrap().name, "renamed");
+        assert_eq!(*copy.resource(&id).unwrap(), 60);
+
+        company.ceo_mut().unwrap().name = "boss".to_string();
+        assert_eq!(copy.ceo().unwrap().name, "ceo");
+        assert_eq!(company.ceo().unwrap().name, "boss");
+    }
+
+    #[test]
+    fn test_snapshot_after_every_change() {
+        let ceo = Manager::new("ceo");
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let ids: Vec<Uuid> = (0..1000)
+            .map(|_| company.hire(Manager::new("staff"), &ceo_id).unwrap())
+            .collect();
+        company.mint(1_000_000);
+
+        let mut snapshots = Vec::new();
+        for (i, id) in ids.iter().enumerate() {
+            snapshots.push(company.snapshot());
+            company.transfer(&ceo_id, id, 1).unwrap();
+            company.reserve(id, 1).unwrap();
+            company.get_mut(id).unwrap().name = format!("paid {i}");
+        }
+
+        for (i, snapshot) in snapshots.iter().enumerate() {
+            let old = snapshot.company();
+            assert_eq!(*old.resource(&ceo_id).unwrap(), 1_000_000 - i as u64);
+            assert_eq!(old.reserved(&ids[i]), 0);
+            assert_eq!(old.get(&ids[i]).unwrap().name, "staff");
+            if i > 0 {
+                assert_eq!(old.reserved(&ids[i - 1]), 1);
+                assert_eq!(
+                    old.get(&ids[i - 1]).unwrap().name,
+                    format!("paid {}", i - 1)
+                );
+            }
+        }
+
+        company.restore(snapshots.swap_remove(0));
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 1_000_000);
+        company.check_supply().unwrap();
+    }
+}
@@ -0,0 +1,253 @@
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use staff_lib::errors::Error;
+    use staff_lib::operation::Operation;
+    use staff_lib::sqlite::rusqlite::Connection;
+    use staff_lib::sqlite::{SqliteCompany, StorageError};
+    use staff_lib::storage::BTreeBackend;
+    use staff_lib::{Company, Fixed, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Manager {
+        id: u32,
+        name: String,
+    }
+
+    impl Manager {
+        fn new(id: u32) -> Self {
+            Self {
+                id,
+                name: format!("manager {id}"),
+            }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    type Stored = SqliteCompany<Manager, Fixed<2>>;
+
+    fn amount(value: &str) -> Fixed<2> {
+        value.parse().unwrap()
+    }
+
+    fn populate(stored: &mut Stored) {
+        stored.mint(amount("1000")).unwrap();
+        stored.hire(Manager::new(1), &0).unwrap();
+        stored.hire(Manager::new(2), &1).unwrap();
+        stored.hire(Manager::new(3), &1).unwrap();
+        stored.transfer(&0, &3, amount("12.34")).unwrap();
+        stored.change_supervisor(&3, &0).unwrap();
+        stored.withdraw(&2, amount("0.01")).unwrap();
+        stored.fire(&1).unwrap();
+    }
+
+    fn assert_same(left: &Company<Manager, Fixed<2>>, right: &Company<Manager, Fixed<2>>) {
+        assert!(left.diff(right).is_empty());
+        assert_eq!(left.total_minted(), right.total_minted());
+        assert_eq!(left.total_burned(), right.total_burned());
+        for staff_id in left.get_all_staff() {
+            assert_eq!(left.get(&staff_id), right.get(&staff_id));
+        }
+    }
+
+    fn count(connection: &Connection, table: &str) -> usize {
+        connection
+            .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sqlite_roundtrip_file() {
+        let path = std::env::temp_dir().join(format!("staff_lib_{}.sqlite", Uuid::new_v4()));
+
+        let mut stored = Stored::create(
+            Connection::open(&path).unwrap(),
+            Company::new(Manager::new(0)),
+        )
+        .unwrap();
+        populate(&mut stored);
+        let (company, connection) = stored.into_parts();
+        drop(connection);
+
+        let reopened = Stored::open(Connection::open(&path).unwrap()).unwrap();
+        assert_same(&company, reopened.company());
+        assert_eq!(reopened.company().supervisor(&2), Some(&0));
+        assert_eq!(*reopened.company().resource(&3).unwrap(), amount("21.34"));
+        assert_eq!(reopened.ledger().unwrap().len(), 8);
+
+        let connection = reopened.connection();
+        assert_eq!(count(connection, "staff"), 3);
+        assert_eq!(count(connection, "balances"), 3);
+        assert_eq!(count(connection, "reporting_lines"), 2);
+
+        connection
+            .execute("DELETE FROM staff WHERE id = '2'", [])
+            .unwrap();
+        assert_eq!(count(connection, "balances"), 2);
+        assert_eq!(count(connection, "reporting_lines"), 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_sqlite_create_stores_existing_company() {
+        let mut company = Company::new(Manager::new(0));
+        company.mint(amount("50"));
+        company.hire(Manager::new(1), &0).unwrap();
+        company.transfer(&0, &1, amount("0.5")).unwrap();
+
+        let stored =
+            Stored::create(Connection::open_in_memory().unwrap(), company.clone()).unwrap();
+        let (_, connection) = stored.into_parts();
+        let reopened = Stored::open(connection).unwrap();
+
+        assert_same(&company, reopened.company());
+        assert!(reopened.ledger().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sqlite_rejected_operation_is_not_stored() {
+        let mut stored = Stored::create(
+            Connection::open_in_memory().unwrap(),
+            Company::new(Manager::new(0)),
+        )
+        .unwrap();
+        populate(&mut stored);
+
+        assert!(matches!(
+            stored.fire(&0),
+            Err(StorageError::Company(Error::CannotFireCeo))
+        ));
+        assert!(matches!(
+            stored.apply(Operation::Transfer {
+                from: 2,
+                to: 0,
+                amount: amount("100"),
+            }),
+            Err(StorageError::Company(Error::InsufficientResourcesError {
+                staff_id: 2,
+                ..
+            }))
+        ));
+        assert_eq!(stored.ledger().unwrap().len(), 8);
+
+        let (company, connection) = stored.into_parts();
+        let reopened = Stored::open(connection).unwrap();
+        assert_same(&company, reopened.company());
+    }
+
+    #[test]
+    fn test_sqlite_failed_write_rolls_back() {
+        let mut stored = Stored::create(
+            Connection::open_in_memory().unwrap(),
+            Company::new(Manager::new(0)),
+        )
+        .unwrap();
+        stored.mint(amount("10")).unwrap();
+        stored
+            .connection()
+            .execute_batch("DROP TABLE ledger")
+            .unwrap();
+
+        assert!(matches!(
+            stored.hire(Manager::new(1), &0),
+            Err(StorageError::Sqlite(_))
+        ));
+        assert!(stored.company().get(&1).is_none());
+    }
+
+    #[test]
+    fn test_sqlite_open_detects_corruption() {
+        let mut stored = Stored::create(
+            Connection::open_in_memory().unwrap(),
+            Company::new(Manager::new(0)),
+        )
+        .unwrap();
+        populate(&mut stored);
+        let (_, connection) = stored.into_parts();
+
+        connection
+            .execute(
+                "UPDATE balances SET balance = '\"1.00\"' WHERE staff_id = '3'",
+                [],
+            )
+            .unwrap();
+        assert!(matches!(
+            Stored::open(connection),
+            Err(StorageError::Corrupted(_))
+        ));
+
+        let mut stored = Stored::create(
+            Connection::open_in_memory().unwrap(),
+            Company::new(Manager::new(0)),
+        )
+        .unwrap();
+        populate(&mut stored);
+        let (_, connection) = stored.into_parts();
+
+        connection
+            .execute(
+                "INSERT INTO reporting_lines (staff_id, supervisor_id) VALUES ('0', '3')",
+                [],
+            )
+            .unwrap();
+        assert!(matches!(
+            Stored::open(connection),
+            Err(StorageError::Corrupted(_))
+        ));
+    }
+
+    #[test]
+    fn test_sqlite_company_of_other_backend() {
+        type Sorted = SqliteCompany<Manager, Fixed<2>, BTreeBackend>;
+
+        let mut stored = Sorted::create(
+            Connection::open_in_memory().unwrap(),
+            Company::new(Manager::new(0)),
+        )
+        .unwrap();
+        stored.mint(amount("10")).unwrap();
+        stored.hire(Manager::new(2), &0).unwrap();
+        stored.hire(Manager::new(1), &2).unwrap();
+        stored.fire(&2).unwrap();
+
+        let (_, connection) = stored.into_parts();
+        let reopened = Sorted::open(connection).unwrap();
+        assert_eq!(reopened.company().get_all_staff(), vec![0, 1]);
+        assert_eq!(reopened.company().supervisor(&1), Some(&0));
+        assert_eq!(reopened.ledger().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_unpersisted_state_is_rejected() {
+        let mut company = Company::new(Manager::new(0));
+        company.mint(amount("1000"));
+        let reservation_id = company.reserve(&0, amount("500")).unwrap();
+
+        let connection = Connection::open_in_memory().unwrap();
+        assert!(matches!(
+            Stored::create(connection, company.clone()),
+            Err(StorageError::UnpersistedState)
+        ));
+
+        company.release(&reservation_id).unwrap();
+        let connection = Connection::open_in_memory().unwrap();
+        let mut stored = Stored::create(connection, company).unwrap();
+        stored.hire(Manager::new(1), &0).unwrap();
+
+        let (_, connection) = stored.into_parts();
+        let reopened = Stored::open(connection).unwrap();
+        assert_eq!(*reopened.company().resource(&0).unwrap(), amount("900"));
+        assert_eq!(*reopened.company().resource(&1).unwrap(), amount("100"));
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::{Company, Fixed, StaffEntity};
+    use std::collections::BTreeMap;
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: Uuid,
+    }
+
+    impl Manager {
+        fn new() -> Self {
+            Self { id: Uuid::new_v4() }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_stats_of_single_ceo() {
+        let mut company: Company<Manager, u64> = Company::new(Manager::new());
+        company.mint(100);
+
+        let stats = company.stats();
+        assert_eq!(stats.headcount, 1);
+        assert_eq!(stats.max_depth, 0);
+        assert_eq!(stats.average_depth, 0.0);
+        assert!(stats.span_of_control.is_empty());
+        assert_eq!(stats.leaves, 1);
+        assert_eq!(stats.balances.min, 100);
+        assert_eq!(stats.balances.max, 100);
+        assert_eq!(stats.balances.median, 100);
+        assert_eq!(stats.balances.gini, 0.0);
+        assert!(stats.single_report_managers.is_empty());
+    }
+
+    #[test]
+    fn test_stats_of_hierarchy() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        // ceo -> (first -> (third), second)
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let third_id = company.hire(Manager::new(), &first_id).unwrap();
+
+        company.mint(100);
+        company.transfer(&ceo_id, &first_id, 30).unwrap();
+        company.transfer(&ceo_id, &second_id, 10).unwrap();
+        company.transfer(&ceo_id, &third_id, 60).unwrap();
+
+        let stats = company.stats();
+        assert_eq!(stats.headcount, 4);
+        assert_eq!(stats.max_depth, 2);
+        assert_eq!(stats.average_depth, 1.0);
+        assert_eq!(stats.span_of_control, BTreeMap::from([(1, 1), (2, 1)]));
+        assert_eq!(stats.leaves, 2);
+        assert_eq!(stats.single_report_managers, vec![first_id]);
+
+        assert_eq!(stats.balances.min, 0);
+        assert_eq!(stats.balances.max, 60);
+        assert_eq!(stats.balances.median, 20);
+        // sorted balances 0, 10, 30, 60: (-3 * 0 - 10 + 30 + 3 * 60) / (4 * 100)
+        assert!((stats.balances.gini - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_of_fixed_balances() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, Fixed<2>> = Company::new(ceo);
+        let id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint("1".parse().unwrap());
+        company
+            .transfer(&ceo_id, &id, "0.25".parse().unwrap())
+            .unwrap();
+
+        let stats = company.stats();
+        assert_eq!(stats.balances.median.to_string(), "0.50");
+        assert!((stats.balances.gini - 0.25).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::errors::Error;
+    use staff_lib::storage::{
+        BTreeBackend, Backend, DenseBackend, DenseMap, HashBackend, PersistentBackend, StaffMap,
+    };
+    use staff_lib::{Company, StaffEntity};
+    use std::collections::HashSet;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: u32,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn run<B: Backend<u32>>() -> Company<Manager, u64, B> {
+        let mut company: Company<Manager, u64, B> = Company::new(Manager { id: 4 });
+        company.mint(1000);
+        company.hire(Manager { id: 9 }, &4).unwrap();
+        company.hire(Manager { id: 2 }, &9).unwrap();
+        company.hire(Manager { id: 7 }, &9).unwrap();
+        company.hire(Manager { id: 0 }, &2).unwrap();
+        company.transfer(&4, &7, 15).unwrap();
+        company.withdraw(&9, 20).unwrap();
+        company.change_supervisor(&0, &7).unwrap();
+        company.fire(&9).unwrap();
+        assert!(matches!(
+            company.fire(&9),
+            Err(Error::StaffNotFound { staff_id: 9 })
+        ));
+        company
+    }
+
+    fn summary<B: Backend<u32>>(
+        company: &Company<Manager, u64, B>,
+    ) -> Vec<(u32, Option<u32>, u64)> {
+        let mut ids = company.get_all_staff();
+        ids.sort();
+        ids.into_iter()
+            .map(|id| {
+                (
+                    id,
+                    company.supervisor(&id).copied(),
+                    *company.resource(&id).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_backends_behave_the_same() {
+        let hash = run::<HashBackend>();
+        let btree = run::<BTreeBackend>();
+        let dense = run::<DenseBackend>();
+        let persistent = run::<PersistentBackend>();
+
+        assert_eq!(
+            summary(&hash),
+            vec![
+                (0, Some(7), 1),
+                (2, Some(4), 9),
+                (4, None, 946),
+                (7, Some(4), 24),
+            ]
+        );
+        assert_eq!(summary(&btree), summary(&hash));
+        assert_eq!(summary(&dense), summary(&hash));
+        assert_eq!(summary(&persistent), summary(&hash));
+        assert!(dense.check_supply().is_ok());
+        assert_eq!(dense.subordinates(&7).unwrap().len(), 1);
+
+        let subordinates: &HashSet<u32> = persistent.subordinates(&4).unwrap();
+        assert_eq!(subordinates, &HashSet::from([2, 7]));
+    }
+
+    #[test]
+    fn test_btree_backend_lists_staff_by_id() {
+        let company = run::<BTreeBackend>();
+        assert_eq!(company.get_all_staff(), vec![0, 2, 4, 7]);
+        let subordinates: Vec<u32> = company
+            .subordinate_set(&4)
+            .unwrap()
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(subordinates, vec![2, 7]);
+
+        let mut copy = company.clone();
+        let snapshot = copy.snapshot();
+        copy.hire(Manager { id: 1 }, &0).unwrap();
+        assert_eq!(company.diff(&copy).added, vec![1]);
+        copy.restore(snapshot);
+        assert!(company.diff(&copy).is_empty());
+    }
+
+    #[test]
+    fn test_dense_map() {
+        let mut map: DenseMap<u32, &str> = DenseMap::default();
+        assert!(map.is_empty());
+        assert_eq!(map.insert(3, "c"), None);
+        assert_eq!(map.insert(1, "a"), None);
+        assert_eq!(map.insert(3, "C"), Some("c"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&3), Some(&"C"));
+        assert_eq!(map.get(&2), None);
+        assert_eq!(map.get(&100), None);
+
+        *map.get_or_default(&5) = "e";
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![1, 3, 5]);
+        assert_eq!(map.remove(&1), Some("a"));
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), vec!["C", "e"]);
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn test_dense_map_sparse_ids() {
+        let mut map: DenseMap<i64, &str> = DenseMap::default();
+        assert_eq!(map.insert(-1, "negative"), None);
+        assert_eq!(map.insert(i64::MAX, "huge"), None);
+        assert_eq!(map.insert(100, "far"), None);
+        assert_eq!(map.insert(2, "b"), None);
+        assert_eq!(map.insert(100, "Far"), Some("far"));
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.get(&-1), Some(&"negative"));
+        assert_eq!(map.get(&i64::MAX), Some(&"huge"));
+        assert_eq!(map.keys().next(), Some(&2));
+
+        // growing past 100 moves it to the vector
+        *map.get_or_default(&70) = "g";
+        assert_eq!(
+            map.keys().take(3).copied().collect::<Vec<_>>(),
+            vec![2, 70, 100]
+        );
+        assert_eq!(map.get(&100), Some(&"Far"));
+        assert_eq!(map.remove(&i64::MAX), Some("huge"));
+        assert_eq!(map.remove(&i64::MAX), None);
+        assert_eq!(map.remove(&100), Some("Far"));
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn test_dense_backend_hires_any_id() {
+        let mut company: Company<Manager, u64, DenseBackend> = Company::new(Manager { id: 1 });
+        company.mint(100);
+        company.hire(Manager { id: u32::MAX }, &1).unwrap();
+        company.hire(Manager { id: 5 }, &u32::MAX).unwrap();
+        assert_eq!(company.supervisor(&5), Some(&u32::MAX));
+        company.fire(&u32::MAX).unwrap();
+        assert_eq!(company.supervisor(&5), Some(&1));
+        assert_eq!(company.get_all_staff().len(), 2);
+        company.check_supply().unwrap();
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::errors::Error;
+    use staff_lib::structure::{Move, StructureRule, StructureRules, Violation};
+    use staff_lib::{Company, StaffEntity};
+
+    #[derive(Debug)]
+    struct Manager {
+        id: u32,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn hire(company: &mut Company<Manager, u64>, id: u32, supervisor_id: u32) {
+        company.hire(Manager { id }, &supervisor_id).unwrap();
+    }
+
+    #[test]
+    fn test_hire_respects_max_reports_and_depth() {
+        let mut company: Company<Manager, u64> = Company::new(Manager { id: 0 });
+        company.set_structure_rules(StructureRules {
+            max_reports: Some(2),
+            max_depth: Some(2),
+            ..Default::default()
+        });
+
+        hire(&mut company, 1, 0);
+        hire(&mut company, 2, 0);
+        hire(&mut company, 3, 1);
+
+        assert!(matches!(
+            company.hire(Manager { id: 4 }, &0),
+            Err(Error::StructureViolation {
+                staff_id: 0,
+                rule: StructureRule::MaxReports,
+                limit: 2,
+                attempted: 3,
+            })
+        ));
+        assert!(matches!(
+            company.hire(Manager { id: 4 }, &3),
+            Err(Error::StructureViolation {
+                staff_id: 4,
+                rule: StructureRule::MaxDepth,
+                limit: 2,
+                attempted: 3,
+            })
+        ));
+        assert!(company.get(&4).is_none());
+        assert!(company.violations().is_empty());
+    }
+
+    #[test]
+    fn test_change_supervisor_respects_rules() {
+        let mut company: Company<Manager, u64> = Company::new(Manager { id: 0 });
+        hire(&mut company, 1, 0);
+        hire(&mut company, 2, 0);
+        hire(&mut company, 3, 1);
+        hire(&mut company, 4, 1);
+        hire(&mut company, 5, 3);
+        company.set_structure_rules(StructureRules {
+            max_reports: Some(2),
+            max_depth: Some(3),
+            min_reports: Some(2),
+        });
+
+        // the moved subtree of 3 would end at depth 4
+        assert!(matches!(
+            company.change_supervisor(&3, &4),
+            Err(Error::StructureViolation {
+                staff_id: 3,
+                rule: StructureRule::MaxDepth,
+                attempted: 4,
+                ..
+            })
+        ));
+        // 1 would be left with a single report
+        assert!(matches!(
+            company.change_supervisor(&4, &2),
+            Err(Error::StructureViolation {
+                staff_id: 1,
+                rule: StructureRule::MinReports,
+                attempted: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            company.change_supervisor(&5, &1),
+            Err(Error::StructureViolation {
+                staff_id: 1,
+                rule: StructureRule::MaxReports,
+                ..
+            })
+        ));
+
+        company.change_supervisor(&5, &2).unwrap();
+        assert_eq!(company.supervisor(&5), Some(&2));
+    }
+
+    #[test]
+    fn test_rebalance() {
+        let mut company: Company<Manager, u64> = Company::new(Manager { id: 0 });
+        for id in 1..=5 {
+            hire(&mut company, id, 0);
+        }
+        hire(&mut company, 6, 1);
+        hire(&mut company, 7, 2);
+        hire(&mut company, 8, 7);
+        hire(&mut company, 9, 8);
+
+        company.set_structure_rules(StructureRules {
+            max_reports: Some(3),
+            max_depth: Some(2),
+            min_reports: Some(2),
+        });
+        let violation = |staff_id, rule, limit, actual| Violation {
+            staff_id,
+            rule,
+            limit,
+            actual,
+        };
+        assert_eq!(
+            company.violations(),
+            vec![
+                violation(0, StructureRule::MaxReports, 3, 5),
+                violation(1, StructureRule::MinReports, 2, 1),
+                violation(2, StructureRule::MinReports, 2, 1),
+                violation(7, StructureRule::MinReports, 2, 1),
+                violation(8, StructureRule::MaxDepth, 2, 3),
+                violation(8, StructureRule::MinReports, 2, 1),
+                violation(9, StructureRule::MaxDepth, 2, 4),
+            ]
+        );
+
+        let plan = company.rebalance_plan();
+        assert_eq!(company.supervisor(&9), Some(&8));
+
+        let step = |staff_id, from, to| Move { staff_id, from, to };
+        assert_eq!(
+            plan,
+            vec![step(8, 7, 2), step(9, 8, 2), step(5, 0, 1), step(4, 0, 1)]
+        );
+        assert_eq!(company.rebalance(), plan);
+        assert!(company.violations().is_empty());
+
+        for (supervisor_id, subordinates) in [(0, [1, 2, 3]), (1, [4, 5, 6]), (2, [7, 8, 9])] {
+            for id in subordinates {
+                assert_eq!(company.supervisor(&id), Some(&supervisor_id));
+            }
+        }
+    }
+
+    #[test]
+    fn test_change_supervisor_in_long_chain() {
+        const LENGTH: u32 = 200_000;
+
+        let mut company: Company<Manager, u64> = Company::new(Manager { id: 0 });
+        for id in 1..=LENGTH {
+            hire(&mut company, id, id - 1);
+        }
+
+        company.change_supervisor(&2, &0).unwrap();
+        assert_eq!(company.supervisor(&2), Some(&0));
+
+        company.set_structure_rules(StructureRules {
+            max_depth: Some(10),
+            ..Default::default()
+        });
+        assert!(matches!(
+            company.change_supervisor(&5, &1),
+            Err(Error::StructureViolation {
+                staff_id: 5,
+                rule: StructureRule::MaxDepth,
+                limit: 10,
+                attempted,
+            }) if attempted == LENGTH as usize - 3
+        ));
+    }
+}
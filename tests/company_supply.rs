@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::{Company, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: Uuid,
+    }
+
+    impl Manager {
+        fn new() -> Self {
+            Self { id: Uuid::new_v4() }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_supply_accounting() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        company.mint(1000);
+        company.mint(500);
+
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let worker_id = company.hire(Manager::new(), &manager_id).unwrap();
+        company.transfer(&ceo_id, &worker_id, 100).unwrap();
+        company
+            .distribute(&ceo_id, &[(manager_id, 1), (worker_id, 2)], 100)
+            .unwrap();
+
+        assert_eq!(company.total_minted(), 1500);
+        assert_eq!(company.total_burned(), 0);
+        assert_eq!(company.total_supply(), 1500);
+        company.check_supply().unwrap();
+
+        company.withdraw(&worker_id, 50).unwrap();
+        company.fire(&manager_id).unwrap();
+
+        assert_eq!(company.total_burned(), 50);
+        assert_eq!(company.total_supply(), 1450);
+        company.check_supply().unwrap();
+
+        company.fire(&worker_id).unwrap();
+        company.sweep_dust(&ceo_id).unwrap();
+        assert_eq!(*company.resource(&ceo_id).unwrap(), company.total_supply());
+    }
+}
@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::sweep::{SweepAmount, SweepRule, SweepTarget};
+    use staff_lib::{errors::Error, Company, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: Uuid,
+    }
+
+    impl Manager {
+        fn new() -> Self {
+            Self { id: Uuid::new_v4() }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_sweep_to_supervisor() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let worker_id = company.hire(Manager::new(), &manager_id).unwrap();
+        company.mint(1000);
+        company.transfer(&ceo_id, &manager_id, 300).unwrap();
+        company.transfer(&ceo_id, &worker_id, 200).unwrap();
+
+        let rule = SweepRule {
+            amount: SweepAmount::AboveFloor(50),
+            target: SweepTarget::Supervisor,
+        };
+        let report = company.sweep_to(&ceo_id, &rule).unwrap();
+
+        assert_eq!(report.swept.len(), 2);
+        assert_eq!(report.total, 250 + 150);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 750);
+        assert_eq!(*company.resource(&manager_id).unwrap(), 200);
+        assert_eq!(*company.resource(&worker_id).unwrap(), 50);
+    }
+
+    #[test]
+    fn test_sweep_to_root() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let manager_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let worker_id = company.hire(Manager::new(), &manager_id).unwrap();
+        company.mint(1000);
+        company.transfer(&ceo_id, &manager_id, 300).unwrap();
+        company.transfer(&ceo_id, &worker_id, 200).unwrap();
+        company.reserve(&worker_id, 100).unwrap();
+
+        let rule = SweepRule {
+            amount: SweepAmount::Percentage(50),
+            target: SweepTarget::Root,
+        };
+        let report = company.sweep_to(&manager_id, &rule).unwrap();
+        assert_eq!(report.total, 50);
+        assert_eq!(*company.resource(&manager_id).unwrap(), 350);
+
+        let rule = SweepRule {
+            amount: SweepAmount::All,
+            target: SweepTarget::Root,
+        };
+        let report = company.sweep_to(&ceo_id, &rule).unwrap();
+        assert_eq!(report.total, 350 + 50);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 900);
+        assert_eq!(*company.resource(&worker_id).unwrap(), 100);
+
+        assert!(matches!(
+            company.sweep_to(&Uuid::new_v4(), &rule),
+            Err(Error::StaffNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sweep_invalid_percentage() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let worker_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(1000);
+        company.transfer(&ceo_id, &worker_id, 200).unwrap();
+        company.reserve(&worker_id, 150).unwrap();
+
+        let rule = SweepRule {
+            amount: SweepAmount::Percentage(150),
+            target: SweepTarget::Supervisor,
+        };
+        assert!(matches!(
+            company.sweep_to(&ceo_id, &rule),
+            Err(Error::InvalidPercentage { percentage: 150 })
+        ));
+        assert_eq!(*company.resource(&worker_id).unwrap(), 200);
+
+        let rule = SweepRule {
+            amount: SweepAmount::Percentage(100),
+            ..rule
+        };
+        let report = company.sweep_to(&ceo_id, &rule).unwrap();
+        assert_eq!(report.total, 50);
+        assert_eq!(*company.resource(&worker_id).unwrap(), 150);
+        assert_eq!(company.available(&worker_id), Some(0));
+        company.check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_sweep_percentage_does_not_overflow() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u32> = Company::new(ceo);
+        let worker_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(4_000_000_000);
+        company
+            .transfer(&ceo_id, &worker_id, 4_000_000_000)
+            .unwrap();
+
+        let rule = SweepRule {
+            amount: SweepAmount::Percentage(50),
+            target: SweepTarget::Supervisor,
+        };
+        let report = company.sweep_to(&ceo_id, &rule).unwrap();
+        assert_eq!(report.total, 2_000_000_000);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 2_000_000_000);
+        assert_eq!(*company.resource(&worker_id).unwrap(), 2_000_000_000);
+        company.check_supply().unwrap();
+    }
+}
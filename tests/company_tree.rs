@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::{Company, StaffEntity};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: u32,
+        name: &'static str,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn company() -> Company<Manager, u64> {
+        let mut company = Company::new(Manager { id: 0, name: "CEO" });
+        company.mint(1000);
+        company.hire(Manager { id: 3, name: "Ann" }, &0).unwrap();
+        company.hire(Manager { id: 1, name: "Bob" }, &0).unwrap();
+        company.hire(Manager { id: 2, name: "Eve" }, &1).unwrap();
+        company.hire(Manager { id: 4, name: "Dan" }, &2).unwrap();
+        company.hire(Manager { id: 5, name: "Zoe" }, &1).unwrap();
+        company
+    }
+
+    #[test]
+    fn test_display_tree_sorted_by_id() {
+        let company = company();
+
+        let tree = company.display_tree(|manager| manager.name.to_string());
+        assert_eq!(
+            tree.to_string(),
+            "CEO\n\
+             ├── Bob\n\
+             │   ├── Eve\n\
+             │   │   └── Dan\n\
+             │   └── Zoe\n\
+             └── Ann\n"
+        );
+    }
+
+    #[test]
+    fn test_display_tree_key_depth_and_balances() {
+        let company = company();
+
+        let tree = company
+            .display_tree(|manager| manager.name.to_string())
+            .sort_by_key(|manager| manager.name)
+            .max_depth(2)
+            .with_balances();
+        assert_eq!(
+            tree.to_string(),
+            "CEO (810)\n\
+             ├── Ann (100)\n\
+             └── Bob (73)\n\
+             \x20   ├── Eve (9)\n\
+             \x20   └── Zoe (8)\n"
+        );
+
+        let tree = company
+            .display_tree(|manager| manager.id.to_string())
+            .max_depth(0);
+        assert_eq!(tree.to_string(), "0\n");
+    }
+
+    #[test]
+    fn test_display_tree_of_long_chain() {
+        let mut company: Company<Manager, u64> = Company::new(Manager { id: 0, name: "CEO" });
+        for id in 1..=2000 {
+            company
+                .hire(Manager { id, name: "Ann" }, &(id - 1))
+                .unwrap();
+        }
+
+        // a recursive rendering overflows the small stack
+        let tree = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(move || {
+                company
+                    .display_tree(|manager| manager.name.to_string())
+                    .to_string()
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(lines.len(), 2001);
+        assert_eq!(lines[2000], format!("{}└── Ann", "    ".repeat(1999)));
+    }
+}
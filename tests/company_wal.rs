@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use staff_lib::errors::Error;
+    use staff_lib::storage::BTreeBackend;
+    use staff_lib::wal::{DurableCompany, WalError};
+    use staff_lib::{Company, Fixed, StaffEntity};
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Manager {
+        id: u32,
+        name: String,
+    }
+
+    impl Manager {
+        fn new(id: u32) -> Self {
+            Self {
+                id,
+                name: format!("manager {id}"),
+            }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    type Durable = DurableCompany<Manager, Fixed<2>>;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("staff_lib_wal_{}", Uuid::new_v4()))
+    }
+
+    fn amount(value: &str) -> Fixed<2> {
+        value.parse().unwrap()
+    }
+
+    fn populate(durable: &mut Durable) {
+        durable.mint(amount("1000")).unwrap();
+        durable.hire(Manager::new(1), &0).unwrap();
+        durable.hire(Manager::new(2), &1).unwrap();
+        durable.hire(Manager::new(3), &1).unwrap();
+        durable.transfer(&0, &3, amount("12.34")).unwrap();
+        durable.change_supervisor(&3, &0).unwrap();
+        durable.withdraw(&2, amount("0.01")).unwrap();
+        durable.fire(&1).unwrap();
+    }
+
+    fn wal_lines(dir: &Path) -> usize {
+        fs::read_to_string(dir.join("wal.jsonl"))
+            .unwrap()
+            .lines()
+            .count()
+    }
+
+    #[test]
+    fn test_reopen_replays_log() {
+        let dir = temp_dir();
+        let mut durable = Durable::create(&dir, Company::new(Manager::new(0))).unwrap();
+        populate(&mut durable);
+
+        assert!(matches!(
+            durable.fire(&0),
+            Err(WalError::Company(Error::CannotFireCeo))
+        ));
+        assert_eq!(durable.seq(), 8);
+        assert_eq!(wal_lines(&dir), 8);
+
+        let expected = durable.into_company();
+        let reopened: Durable = Company::open(&dir).unwrap();
+        assert_eq!(reopened.seq(), 8);
+        assert!(expected.diff(reopened.company()).is_empty());
+        assert_eq!(reopened.company().total_supply(), amount("999.99"));
+        assert_eq!(reopened.company().get(&2), Some(&Manager::new(2)));
+
+        assert!(matches!(
+            Durable::create(&dir, Company::new(Manager::new(0))),
+            Err(WalError::Io(_))
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_torn_last_record_is_dropped() {
+        let dir = temp_dir();
+        let mut durable = Durable::create(&dir, Company::new(Manager::new(0))).unwrap();
+        populate(&mut durable);
+        let expected = durable.into_company();
+
+        let mut log = OpenOptions::new()
+            .append(true)
+            .open(dir.join("wal.jsonl"))
+            .unwrap();
+        log.write_all(br#"{"seq":9,"operation":{"Mint":{"amo"#)
+            .unwrap();
+        drop(log);
+
+        let mut reopened: Durable = Company::open(&dir).unwrap();
+        assert!(expected.diff(reopened.company()).is_empty());
+        assert_eq!(wal_lines(&dir), 8);
+
+        reopened.mint(amount("1")).unwrap();
+        let reopened: Durable = Company::open(&dir).unwrap();
+        assert_eq!(reopened.seq(), 9);
+        assert_eq!(reopened.company().total_supply(), amount("1000.99"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_corrupted_record_is_reported() {
+        let dir = temp_dir();
+        let mut durable = Durable::create(&dir, Company::new(Manager::new(0))).unwrap();
+        populate(&mut durable);
+        drop(durable);
+
+        let log = fs::read_to_string(dir.join("wal.jsonl")).unwrap();
+        let mut lines: Vec<&str> = log.lines().collect();
+        lines[2] = "garbage";
+        fs::write(dir.join("wal.jsonl"), lines.join("\n") + "\n").unwrap();
+
+        let result: Result<Durable, _> = Company::open(&dir);
+        assert!(matches!(result, Err(WalError::Corrupted { line: 3, .. })));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_compaction() {
+        let dir = temp_dir();
+        let mut durable = Durable::create(&dir, Company::new(Manager::new(0)))
+            .unwrap()
+            .with_compaction_interval(3);
+        populate(&mut durable);
+        assert_eq!(wal_lines(&dir), 2);
+
+        // a crash between writing the snapshot and emptying the log leaves stale records
+        let stale = fs::read(dir.join("wal.jsonl")).unwrap();
+        durable.compact().unwrap();
+        assert_eq!(wal_lines(&dir), 0);
+        fs::write(dir.join("wal.jsonl"), stale).unwrap();
+
+        let expected = durable.into_company();
+        let reopened: Durable = Company::open(&dir).unwrap();
+        assert_eq!(reopened.seq(), 8);
+        assert!(expected.diff(reopened.company()).is_empty());
+        reopened.company().check_supply().unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_compaction_keeps_the_operation() {
+        let dir = temp_dir();
+        let mut durable = Durable::create(&dir, Company::new(Manager::new(0)))
+            .unwrap()
+            .with_compaction_interval(2);
+        fs::remove_dir_all(&dir).unwrap();
+
+        durable.mint(amount("1")).unwrap();
+        durable.hire(Manager::new(1), &0).unwrap();
+        assert!(durable.compaction_error().is_some());
+        assert_eq!(durable.seq(), 2);
+
+        fs::create_dir(&dir).unwrap();
+        durable.transfer(&0, &1, amount("0.5")).unwrap();
+        assert!(durable.compaction_error().is_none());
+
+        let expected = durable.into_company();
+        let reopened: Durable = Company::open(&dir).unwrap();
+        assert_eq!(reopened.seq(), 3);
+        assert!(expected.diff(reopened.company()).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_durable_company_of_other_backend() {
+        type Sorted = DurableCompany<Manager, Fixed<2>, BTreeBackend>;
+
+        let dir = temp_dir();
+        let mut durable = Sorted::create(&dir, Company::new(Manager::new(0))).unwrap();
+        durable.mint(amount("10")).unwrap();
+        durable.hire(Manager::new(2), &0).unwrap();
+        durable.hire(Manager::new(1), &2).unwrap();
+        durable.fire(&2).unwrap();
+        durable.compact().unwrap();
+        durable.hire(Manager::new(3), &1).unwrap();
+
+        let reopened: Sorted = Company::open(&dir).unwrap();
+        assert_eq!(reopened.company().get_all_staff(), vec![0, 1, 3]);
+        assert_eq!(reopened.company().supervisor(&3), Some(&1));
+        reopened.company().check_supply().unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_unpersisted_state_is_rejected() {
+        let dir = temp_dir();
+        let mut company = Company::new(Manager::new(0));
+        company.mint(amount("1000"));
+        let reservation_id = company.reserve(&0, amount("500")).unwrap();
+
+        // the reservation would be lost, so hires replayed after reopening would differ
+        assert!(matches!(
+            Durable::create(&dir, company.clone()),
+            Err(WalError::UnpersistedState)
+        ));
+        assert!(!dir.join("snapshot.json").exists());
+        assert!(matches!(
+            Durable::create(&dir, Company::new(Manager::new(0)).with_insertion_order()),
+            Err(WalError::UnpersistedState)
+        ));
+
+        company.release(&reservation_id).unwrap();
+        let mut durable = Durable::create(&dir, company).unwrap();
+        durable.hire(Manager::new(1), &0).unwrap();
+        assert_eq!(*durable.company().resource(&0).unwrap(), amount("900"));
+
+        let reopened: Durable = Company::open(&dir).unwrap();
+        assert_eq!(*reopened.company().resource(&0).unwrap(), amount("900"));
+        assert_eq!(*reopened.company().resource(&1).unwrap(), amount("100"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
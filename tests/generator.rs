@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand_chacha::ChaCha8Rng;
+    use staff_lib::generator::{Branching, CompanyConfig, Generator, OperationWeights};
+    use staff_lib::{Company, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: Uuid,
+    }
+
+    impl Manager {
+        fn random(rng: &mut ChaCha8Rng) -> Self {
+            Self {
+                id: Uuid::from_u128(rng.gen()),
+            }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn state(company: &Company<Manager, u64>) -> Vec<(Uuid, Option<Uuid>, u64)> {
+        let mut state: Vec<_> = company
+            .get_all_staff()
+            .into_iter()
+            .map(|id| {
+                (
+                    id,
+                    company.supervisor(&id).cloned(),
+                    *company.resource(&id).unwrap(),
+                )
+            })
+            .collect();
+        state.sort();
+        state
+    }
+
+    fn depth(company: &Company<Manager, u64>, id: &Uuid) -> usize {
+        let mut depth = 0;
+        let mut current = id;
+        while let Some(supervisor_id) = company.supervisor(current) {
+            depth += 1;
+            current = supervisor_id;
+        }
+        depth
+    }
+
+    fn simulate(seed: u64) -> Company<Manager, u64> {
+        let config = CompanyConfig {
+            size: 50,
+            max_depth: 4,
+            max_reports: Some(5),
+            branching: Branching::Preferential,
+            initial_mint: 100_000,
+        };
+
+        let mut generator = Generator::new(seed);
+        let mut company = generator.company(&config, Manager::random);
+        for _ in 0..500 {
+            let operation = generator.operation(
+                &company,
+                &OperationWeights::default(),
+                1000,
+                Manager::random,
+            );
+            let _ = company.apply(operation);
+        }
+        company
+    }
+
+    #[test]
+    fn test_generated_company_shape() {
+        for branching in [
+            Branching::Uniform,
+            Branching::Preferential,
+            Branching::Balanced,
+        ] {
+            let config = CompanyConfig {
+                size: 100,
+                max_depth: 3,
+                max_reports: Some(6),
+                branching,
+                initial_mint: 1000,
+            };
+            let company: Company<Manager, u64> =
+                Generator::new(7).company(&config, Manager::random);
+
+            assert_eq!(company.get_all_staff().len(), 100);
+            assert_eq!(company.total_supply(), 1000);
+            for id in company.get_all_staff() {
+                assert!(depth(&company, &id) <= 3);
+                assert!(company.subordinates(&id).map_or(0, |s| s.len()) <= 6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generator_is_reproducible() {
+        let first = simulate(42);
+        let second = simulate(42);
+
+        assert_eq!(state(&first), state(&second));
+        assert_eq!(first.total_supply(), second.total_supply());
+        assert_ne!(state(&first), state(&simulate(43)));
+        first.check_supply().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "already hired IDs in a row")]
+    fn test_generator_rejects_repeating_factory() {
+        let config = CompanyConfig {
+            size: 3,
+            max_depth: 2,
+            max_reports: None,
+            branching: Branching::Uniform,
+            initial_mint: 0,
+        };
+        let _: Company<Manager, u64> =
+            Generator::new(1).company(&config, |_| Manager { id: Uuid::nil() });
+    }
+}
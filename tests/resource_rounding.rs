@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::resource::{allocate, ParseFixedError};
+    use staff_lib::{Company, Fixed, Resource, Rounding, StaffEntity};
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: Uuid,
+    }
+
+    impl Manager {
+        fn new() -> Self {
+            Self { id: Uuid::new_v4() }
+        }
+    }
+
+    impl StaffEntity for Manager {
+        type ID = Uuid;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn hired_share(balance: u64, rounding: Rounding) -> u64 {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo).with_rounding(rounding);
+        company.mint(balance);
+
+        let id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let share = *company.resource(&id).unwrap();
+        assert_eq!(*company.resource(&ceo_id).unwrap() + share, balance);
+        share
+    }
+
+    #[test]
+    fn test_hire_rounding_modes() {
+        assert_eq!(hired_share(1, Rounding::Floor), 0);
+        assert_eq!(hired_share(1, Rounding::Ceil), 1);
+        assert_eq!(hired_share(1, Rounding::HalfEven), 0);
+
+        assert_eq!(hired_share(15, Rounding::Floor), 1);
+        assert_eq!(hired_share(15, Rounding::Ceil), 2);
+        assert_eq!(hired_share(15, Rounding::HalfEven), 2);
+        assert_eq!(hired_share(25, Rounding::HalfEven), 2);
+        assert_eq!(hired_share(26, Rounding::HalfEven), 3);
+    }
+
+    #[test]
+    fn test_fixed_point_hire() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, Fixed<2>> = Company::new(ceo);
+        company.mint("1".parse().unwrap());
+
+        let id = company.hire(Manager::new(), &ceo_id).unwrap();
+        assert_eq!(company.resource(&id).unwrap().to_string(), "0.10");
+        assert_eq!(company.resource(&ceo_id).unwrap().to_string(), "0.90");
+
+        company.fire(&id).unwrap();
+        assert_eq!(company.resource(&ceo_id).unwrap().to_string(), "1.00");
+    }
+
+    #[test]
+    fn test_fixed_point_overflow() {
+        let parse = |s: &str| s.parse::<Fixed<2>>();
+        assert_eq!(parse(&"9".repeat(39)), Err(ParseFixedError));
+        assert_eq!(
+            parse("1701411834604692317316873037158841057.28"),
+            Err(ParseFixedError)
+        );
+        assert_eq!(
+            parse("1701411834604692317316873037158841057.27"),
+            Ok(Fixed::from_raw(i128::MAX))
+        );
+
+        let half = Fixed::<2>::from_raw(i128::MAX / 2);
+        assert_eq!(half * parse("1").unwrap(), half);
+        assert_eq!(half * parse("2").unwrap(), Fixed::from_raw(i128::MAX - 1));
+        assert_eq!(half / parse("0.5").unwrap(), Fixed::from_raw(i128::MAX - 1));
+        assert_eq!(Fixed::from_raw(-half.raw()) / parse("-1").unwrap(), half);
+
+        let one = parse("1").unwrap();
+        let three = parse("3").unwrap();
+        assert_eq!(one.div_rounded(three, Rounding::Ceil).to_string(), "0.34");
+        assert_eq!(
+            parse("-1")
+                .unwrap()
+                .div_rounded(three, Rounding::Floor)
+                .to_string(),
+            "-0.34"
+        );
+        assert_eq!(
+            parse("0.05")
+                .unwrap()
+                .div_rounded(parse("10").unwrap(), Rounding::HalfEven),
+            Fixed::from_raw(0)
+        );
+    }
+
+    #[test]
+    fn test_distribute_dust() {
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+
+        let mut company: Company<Manager, u64> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let third_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(10);
+
+        let recipients = [(first_id, 1), (second_id, 1), (third_id, 1)];
+        let dust = company.distribute(&ceo_id, &recipients, 10).unwrap();
+
+        assert_eq!(dust, 1);
+        assert_eq!(company.dust(), 1);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 0);
+        for id in [first_id, second_id, third_id] {
+            assert_eq!(*company.resource(&id).unwrap(), 3);
+        }
+
+        assert_eq!(company.sweep_dust(&ceo_id).unwrap(), 1);
+        assert_eq!(company.dust(), 0);
+        assert_eq!(*company.resource(&ceo_id).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_mul_div_does_not_overflow() {
+        assert_eq!(
+            u32::MAX.mul_div(u32::MAX, u32::MAX, Rounding::Floor),
+            u32::MAX
+        );
+        assert_eq!(u128::MAX.mul_div(3, 3, Rounding::Floor), u128::MAX);
+        assert_eq!(7u8.mul_div(200, 150, Rounding::Ceil), 10);
+        assert_eq!((-7i64).mul_div(3, 2, Rounding::Floor), -11);
+        assert_eq!((-7i64).mul_div(3, 2, Rounding::Ceil), -10);
+        assert_eq!(i8::MIN.mul_div(1, 1, Rounding::Floor), i8::MIN);
+
+        let allocation = allocate(u64::MAX, &[u64::MAX / 2, u64::MAX / 2], Rounding::Floor);
+        assert_eq!(allocation.shares, vec![u64::MAX / 2; 2]);
+        assert_eq!(allocation.dust, 1);
+
+        let ceo = Manager::new();
+        let ceo_id = ceo.get_id();
+        let mut company: Company<Manager, u32> = Company::new(ceo);
+        let first_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        let second_id = company.hire(Manager::new(), &ceo_id).unwrap();
+        company.mint(u32::MAX);
+
+        let recipients = [(first_id, 3_000_000), (second_id, 1_000_000)];
+        let dust = company
+            .distribute(&ceo_id, &recipients, 4_000_000_000)
+            .unwrap();
+        assert_eq!(dust, 0);
+        assert_eq!(*company.resource(&first_id).unwrap(), 3_000_000_000);
+        assert_eq!(*company.resource(&second_id).unwrap(), 1_000_000_000);
+    }
+}
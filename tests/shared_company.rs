@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use staff_lib::payroll::PayrollRule;
+    use staff_lib::shared::SharedCompany;
+    use staff_lib::storage::BTreeBackend;
+    use staff_lib::{Company, StaffEntity};
+    use std::thread;
+
+    #[derive(Debug)]
+    struct Manager {
+        id: u32,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u32;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_company_is_send_sync() {
+        assert_send_sync::<Company<Manager, u64>>();
+        assert_send_sync::<SharedCompany<Manager, u64>>();
+    }
+
+    #[test]
+    fn test_concurrent_transfers() {
+        let shared = SharedCompany::new(Company::new(Manager { id: 0 }));
+        shared.mint(10_000);
+        for id in 1..=4 {
+            shared.hire(Manager { id }, &0).unwrap();
+        }
+
+        let handles: Vec<_> = (1..=4)
+            .map(|id| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        shared.transfer(&0, &id, 1).unwrap();
+                        shared.transfer(&id, &(id % 4 + 1), 1).unwrap();
+                        assert!(shared.resource(&id).is_some());
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(shared.total_supply(), 10_000);
+        shared.read().check_supply().unwrap();
+        let total: u64 = (1..=4).map(|id| shared.resource(&id).unwrap()).sum();
+        assert_eq!(shared.resource(&0).unwrap() + total, 10_000);
+
+        let company = shared.try_into_inner().ok().unwrap();
+        assert_eq!(company.get_all_staff().len(), 5);
+    }
+
+    #[test]
+    fn test_guards_reach_the_whole_api() {
+        let shared = SharedCompany::new(Company::new(Manager { id: 0 }));
+        shared.mint(1000);
+        shared.hire(Manager { id: 1 }, &0).unwrap();
+
+        {
+            let mut company = shared.write();
+            company.reserve(&0, 800).unwrap();
+            let report = company.run_payroll(&PayrollRule::FixedSalary(200)).unwrap();
+            assert_eq!(report.shortfalls.len(), 1);
+        }
+        assert_eq!(shared.available(&0), Some(100));
+        assert_eq!(shared.read().reserved(&0), 800);
+        shared.read().check_supply().unwrap();
+    }
+
+    #[test]
+    fn test_shared_company_of_other_backend() {
+        let company = Company::<Manager, u64, BTreeBackend>::new(Manager { id: 0 });
+        let shared = SharedCompany::from(company);
+        for id in [3, 1, 2] {
+            shared.hire(Manager { id }, &0).unwrap();
+        }
+        assert_eq!(shared.get_all_staff(), vec![0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use rand_chacha::ChaCha8Rng;
+    use staff_lib::simulation::{Action, Agent, Simulation, TickMetrics};
+    use staff_lib::storage::{BTreeBackend, Backend, DenseBackend, PersistentBackend};
+    use staff_lib::{Company, StaffEntity};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Manager {
+        id: u64,
+    }
+
+    impl StaffEntity for Manager {
+        type ID = u64;
+
+        fn get_id(&self) -> Self::ID {
+            self.id
+        }
+    }
+
+    /// Randomly hires, pays its supervisor or quits.
+    struct RandomAgent;
+
+    impl<B: Backend<u64>> Agent<Manager, u64, B> for RandomAgent {
+        fn act(
+            &mut self,
+            staff_id: &u64,
+            company: &Company<Manager, u64, B>,
+            rng: &mut ChaCha8Rng,
+        ) -> Vec<Action<Manager, u64>> {
+            match rng.gen_range(0..10) {
+                0..=2 => vec![Action::Hire(Manager { id: rng.gen() })],
+                3..=6 => match company.supervisor(staff_id) {
+                    Some(supervisor_id) => vec![Action::Transfer {
+                        to: *supervisor_id,
+                        amount: company.resource(staff_id).unwrap() / 2,
+                    }],
+                    None => vec![],
+                },
+                7 => vec![Action::Quit],
+                _ => vec![],
+            }
+        }
+    }
+
+    fn simulate<B: Backend<u64> + 'static>(seed: u64) -> Vec<TickMetrics<u64>> {
+        let mut company: Company<Manager, u64, B> = Company::new(Manager { id: 0 });
+        company.mint(1_000_000);
+
+        let mut simulation = Simulation::new(company, seed, Box::new(|_, _| Box::new(RandomAgent)));
+        simulation.run(30);
+        assert_eq!(simulation.tick(), 30);
+        simulation.company().check_supply().unwrap();
+        simulation.metrics().to_vec()
+    }
+
+    #[test]
+    fn test_simulation_metrics() {
+        let metrics = simulate::<PersistentBackend>(1);
+
+        assert_eq!(metrics.len(), 30);
+        for (tick, metrics) in metrics.iter().enumerate() {
+            assert_eq!(metrics.tick, tick as u64 + 1);
+            assert_eq!(metrics.balances.len(), metrics.headcount);
+            assert_eq!(metrics.balances.iter().sum::<u64>(), 1_000_000);
+        }
+        assert!(metrics.last().unwrap().headcount > 1);
+        assert!(metrics.iter().any(|metrics| metrics.failed_actions > 0));
+    }
+
+    #[test]
+    fn test_simulation_is_deterministic() {
+        assert_eq!(
+            simulate::<PersistentBackend>(7),
+            simulate::<PersistentBackend>(7)
+        );
+        assert_ne!(
+            simulate::<PersistentBackend>(7),
+            simulate::<PersistentBackend>(8)
+        );
+    }
+
+    #[test]
+    fn test_simulation_on_other_backends() {
+        let metrics = simulate::<PersistentBackend>(3);
+        assert_eq!(simulate::<BTreeBackend>(3), metrics);
+        assert_eq!(simulate::<DenseBackend>(3), metrics);
+    }
+}